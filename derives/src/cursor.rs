@@ -0,0 +1,129 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::DeriveInput;
+
+pub(crate) struct CursorPaginationResponse {
+    item: Ident,
+}
+
+impl CursorPaginationResponse {
+    pub(crate) fn new(input: DeriveInput) -> Self {
+        Self { item: input.ident }
+    }
+
+    pub(crate) fn expand(&self) -> TokenStream {
+        let item = &self.item;
+        let name = Ident::new(
+            &format!("{item}CursorPaginationResponse"),
+            Span::call_site(),
+        );
+
+        quote!(
+            #[derive(
+                Clone,
+                ::serde::Deserialize,
+                ::serde::Serialize,
+                ::utoipa::ToSchema,
+                ::utoipa::IntoResponses)
+            ]
+            #[response(status = 200, description = "OK")]
+            #[serde(rename_all = "camelCase")]
+            pub struct #name {
+                #[schema(example = "3yZe7d")]
+                pub next_cursor: Option<String>,
+                #[schema()]
+                pub data: Vec<#item>,
+            }
+
+            impl #name {
+                pub fn new<K, F>(data: Vec<#item>, limit: u64, key: F) -> Result<Self, ::lighter_common::responses::Error>
+                where
+                    K: ::serde::Serialize,
+                    F: Fn(&#item) -> K,
+                {
+                    let next_cursor = match data.last() {
+                        Some(last) if data.len() as u64 >= limit => {
+                            let cursor = ::lighter_common::cursor::encode(&key(last)).map_err(|e| {
+                                ::lighter_common::responses::Error::InternalServerError {
+                                    message: "Internal server error".to_string(),
+                                }
+                                .with_cause(e)
+                            })?;
+
+                            Some(cursor)
+                        }
+                        _ => None,
+                    };
+
+                    Ok(Self { next_cursor, data })
+                }
+            }
+
+            impl ::actix_web::Responder for #name {
+                type Body = ::actix_web::body::BoxBody;
+
+                fn respond_to(self, _: &::actix_web::HttpRequest) -> ::actix_web::HttpResponse<Self::Body> {
+                    ::actix_web::HttpResponse::Ok().json(self)
+                }
+            }
+        )
+    }
+}
+
+pub(crate) struct CursorPaginationRequest {
+    item: Ident,
+}
+
+impl CursorPaginationRequest {
+    pub(crate) fn new(input: DeriveInput) -> Self {
+        Self { item: input.ident }
+    }
+
+    pub(crate) fn expand(&self) -> TokenStream {
+        let item = &self.item;
+        let name = Ident::new(&format!("{item}CursorPaginationRequest"), Span::call_site());
+
+        quote!(
+            #[derive(
+                Clone,
+                ::serde::Deserialize,
+                ::serde::Serialize,
+                ::utoipa::ToSchema,
+                ::utoipa::IntoParams,
+            )]
+            #[serde(rename_all = "camelCase")]
+            #[into_params(parameter_in = Query)]
+            pub struct #name {
+                #[schema(example = "3yZe7d")]
+                after: Option<String>,
+                #[schema(example = 10)]
+                limit: Option<u64>,
+            }
+
+            impl #name {
+                pub fn after<K: ::serde::de::DeserializeOwned>(&self) -> Result<Option<K>, ::lighter_common::responses::Error> {
+                    self.after
+                        .as_ref()
+                        .map(|cursor| {
+                            ::lighter_common::cursor::decode(cursor).map_err(|_| {
+                                ::lighter_common::responses::Error::BadRequest {
+                                    message: "Invalid cursor".to_string(),
+                                }
+                            })
+                        })
+                        .transpose()
+                }
+
+                pub fn limit(&self) -> u64 {
+                    let limit = self.limit.unwrap_or(10);
+
+                    if limit > 1000 {
+                        1000
+                    } else {
+                        limit
+                    }
+                }
+            }
+        )
+    }
+}
@@ -1,6 +1,7 @@
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
+mod cursor;
 mod pagination;
 
 #[proc_macro_derive(PaginationResponse)]
@@ -10,9 +11,24 @@ pub fn pagination_response_derive(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(PaginationRequest, attributes(order))]
+#[proc_macro_derive(PaginationRequest, attributes(order, pagination, search))]
 pub fn pagination_request_derive(input: TokenStream) -> TokenStream {
     pagination::PaginationRequest::new(parse_macro_input!(input))
+        .map(|request| request.expand())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(CursorPaginationResponse)]
+pub fn cursor_pagination_response_derive(input: TokenStream) -> TokenStream {
+    cursor::CursorPaginationResponse::new(parse_macro_input!(input))
+        .expand()
+        .into()
+}
+
+#[proc_macro_derive(CursorPaginationRequest)]
+pub fn cursor_pagination_request_derive(input: TokenStream) -> TokenStream {
+    cursor::CursorPaginationRequest::new(parse_macro_input!(input))
         .expand()
         .into()
 }
@@ -1,6 +1,6 @@
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::quote;
-use syn::{Data, DeriveInput};
+use syn::{Data, DeriveInput, LitInt, LitStr, Meta};
 
 pub(crate) struct PaginationResponse {
     item: Ident,
@@ -32,10 +32,47 @@ impl PaginationResponse {
                 pub page: u64,
                 #[schema(example = 10)]
                 pub pages: u64,
+                #[schema(example = true)]
+                pub has_next: bool,
+                #[schema(example = false)]
+                pub has_prev: bool,
                 #[schema()]
                 pub data: Vec<#item>,
             }
 
+            impl #name {
+                pub fn new(data: Vec<#item>, total: u64, page: u64, pages: u64) -> Self {
+                    Self {
+                        total,
+                        page,
+                        pages,
+                        has_next: page < pages,
+                        has_prev: page > 1,
+                        data,
+                    }
+                }
+
+                pub async fn from_paginator<'db, C, S>(
+                    paginator: ::lighter_common::prelude::sea_orm::Paginator<'db, C, S>,
+                    page: u64,
+                ) -> Result<Self, ::lighter_common::prelude::sea_orm::DbErr>
+                where
+                    C: ::lighter_common::prelude::sea_orm::ConnectionTrait,
+                    S: ::lighter_common::prelude::sea_orm::SelectorTrait + 'db,
+                    S::Item: Into<#item>,
+                {
+                    let numbers = paginator.num_items_and_pages().await?;
+                    let data = paginator.fetch_page(page.saturating_sub(1)).await?;
+
+                    Ok(Self::new(
+                        data.into_iter().map(Into::into).collect(),
+                        numbers.number_of_items,
+                        page,
+                        numbers.number_of_pages,
+                    ))
+                }
+            }
+
             impl ::actix_web::Responder for #name {
                 type Body = ::actix_web::body::BoxBody;
 
@@ -57,66 +94,199 @@ impl PaginationResponse {
     }
 }
 
+struct Orderable {
+    variant: Ident,
+    rename: Option<String>,
+    column: String,
+}
+
 pub(crate) struct PaginationRequest {
     item: Ident,
-    data: Data,
+    orderables: Vec<Orderable>,
+    default: Option<Ident>,
+    searchables: Vec<String>,
+    max_limit: u64,
+    default_limit: u64,
+    default_page: u64,
+    default_sort: Ident,
 }
 
 impl PaginationRequest {
-    pub(crate) fn new(input: DeriveInput) -> Self {
-        Self {
+    pub(crate) fn new(input: DeriveInput) -> syn::Result<Self> {
+        let mut timestamp_field = None;
+        let mut request = Self {
             item: input.ident,
-            data: input.data,
-        }
-    }
+            orderables: vec![],
+            default: None,
+            searchables: vec![],
+            max_limit: 1000,
+            default_limit: 10,
+            default_page: 1,
+            default_sort: Ident::new("Desc", Span::call_site()),
+        };
+
+        for attr in &input.attrs {
+            if !attr.path().is_ident("pagination") {
+                continue;
+            }
 
-    pub(crate) fn expand(&self) -> TokenStream {
-        let item = &self.item;
-        let name = Ident::new(&format!("{item}PaginationRequest"), Span::call_site());
-        let sort = Ident::new(&format!("{item}PaginationSort"), Span::call_site());
+            // parse #[pagination(max_limit = 200, default_limit = 25, default_page = 1, default_sort = "desc", timestamp_field = "created_at")]
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("max_limit") {
+                    request.max_limit = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                } else if meta.path.is_ident("default_limit") {
+                    request.default_limit = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                } else if meta.path.is_ident("default_page") {
+                    request.default_page = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                } else if meta.path.is_ident("default_sort") {
+                    let sort = meta.value()?.parse::<LitStr>()?;
+
+                    request.default_sort = match sort.value().to_ascii_lowercase().as_str() {
+                        "asc" => Ident::new("Asc", sort.span()),
+                        "desc" => Ident::new("Desc", sort.span()),
+                        _ => {
+                            return Err(syn::Error::new(
+                                sort.span(),
+                                "expected \"asc\" or \"desc\"",
+                            ))
+                        }
+                    };
+                } else if meta.path.is_ident("timestamp_field") {
+                    timestamp_field = Some(meta.value()?.parse::<LitStr>()?);
+                } else {
+                    return Err(meta.error("unsupported pagination attribute"));
+                }
 
-        let order = Ident::new(&format!("{item}PaginationOrder"), Span::call_site());
-        let mut orderables = vec![Ident::new("CreatedAt", Span::call_site())];
-        let mut default = None;
+                Ok(())
+            })?;
+        }
 
-        if let Data::Struct(data) = &self.data {
+        let (variant_name, timestamp_field) = match timestamp_field {
+            Some(field) => (variant(&field, capitalize(field.value()))?, field.value()),
+            None => (
+                Ident::new("CreatedAt", Span::call_site()),
+                "created_at".to_string(),
+            ),
+        };
+        let timestamp = variant_name.to_string();
+
+        request.orderables.push(Orderable {
+            variant: variant_name,
+            rename: None,
+            column: timestamp_field,
+        });
+
+        if let Data::Struct(data) = &input.data {
             for field in &data.fields {
-                // parse #[order]
-                let orderable = field.attrs.iter().any(|attr| attr.path().is_ident("order"));
-                // parse #[order(default)]
-                let is_default = field.attrs.iter().any(|attr| {
+                let mut orderable = false;
+                let mut is_default = false;
+                let mut rename = None;
+
+                for attr in &field.attrs {
+                    // parse #[search]
+                    if attr.path().is_ident("search") {
+                        request
+                            .searchables
+                            .push(field.ident.as_ref().unwrap().to_string());
+                    }
+
                     if !attr.path().is_ident("order") {
-                        return false;
+                        continue;
                     }
 
-                    attr.meta.path().is_ident("default")
-                });
+                    orderable = true;
+
+                    // parse #[order(default, rename = "name")]
+                    if let Meta::List(_) = attr.meta {
+                        attr.parse_nested_meta(|meta| {
+                            if meta.path.is_ident("default") {
+                                is_default = true;
+                            } else if meta.path.is_ident("rename") {
+                                rename = Some(meta.value()?.parse::<LitStr>()?);
+                            } else {
+                                return Err(meta.error("unsupported order attribute"));
+                            }
+
+                            Ok(())
+                        })?;
+                    }
+                }
 
                 if orderable {
-                    let name = field.ident.as_ref().unwrap();
-                    let name = capitalize(name);
-
-                    if name.eq_ignore_ascii_case("CreatedAt") {
+                    let variant = match &rename {
+                        Some(rename) => variant(rename, capitalize(rename.value()))?,
+                        None => Ident::new(
+                            &capitalize(field.ident.as_ref().unwrap()),
+                            Span::call_site(),
+                        ),
+                    };
+
+                    if variant.to_string().eq_ignore_ascii_case(&timestamp) {
                         continue;
                     }
 
-                    let name = Ident::new(&name, Span::call_site());
-
-                    orderables.push(name.clone());
-
                     if is_default {
-                        default = Some(name);
+                        request.default = Some(variant.clone());
                     }
+
+                    request.orderables.push(Orderable {
+                        variant,
+                        rename: rename.as_ref().map(LitStr::value),
+                        column: field.ident.as_ref().unwrap().to_string(),
+                    });
                 }
             }
         }
 
-        let default = default.unwrap_or_else(|| orderables[0].clone());
+        Ok(request)
+    }
+
+    pub(crate) fn expand(&self) -> TokenStream {
+        let item = &self.item;
+        let name = Ident::new(&format!("{item}PaginationRequest"), Span::call_site());
+        let sort = Ident::new(&format!("{item}PaginationSort"), Span::call_site());
+
+        let order = Ident::new(&format!("{item}PaginationOrder"), Span::call_site());
+        let orderables = self.orderables.iter().map(|orderable| {
+            let variant = &orderable.variant;
+
+            match &orderable.rename {
+                Some(rename) => quote!(#[serde(rename = #rename)] #variant),
+                None => quote!(#variant),
+            }
+        });
+        let sorts = Ident::new(&format!("{item}PaginationSorts"), Span::call_site());
+        let variants = self
+            .orderables
+            .iter()
+            .map(|orderable| &orderable.variant)
+            .collect::<Vec<_>>();
+        let columns = self.orderables.iter().map(|orderable| &orderable.column);
+        let keys = self
+            .orderables
+            .iter()
+            .map(|orderable| match &orderable.rename {
+                Some(rename) => rename.clone(),
+                None => uncapitalize(&orderable.variant),
+            })
+            .collect::<Vec<_>>();
+        let default = self
+            .default
+            .clone()
+            .unwrap_or_else(|| self.orderables[0].variant.clone());
+        let searchables = &self.searchables;
+        let max_limit = self.max_limit;
+        let default_limit = self.default_limit;
+        let default_page = self.default_page;
+        let default_sort = &self.default_sort;
+        let minimum = Literal::u64_unsuffixed(1);
+        let maximum = Literal::u64_unsuffixed(max_limit);
+        let example_limit = Literal::u64_unsuffixed(default_limit);
+        let example_page = Literal::u64_unsuffixed(default_page);
 
         quote!(
             #[derive(
                 Clone,
-                ::serde::Deserialize,
                 ::serde::Serialize,
                 ::utoipa::ToSchema
             )]
@@ -131,10 +301,47 @@ impl PaginationRequest {
                 }
             }
 
+            impl #order {
+                pub fn column(&self) -> &'static str {
+                    match self {
+                        #(Self::#variants => #columns,)*
+                    }
+                }
+            }
+
+            impl ::std::str::FromStr for #order {
+                type Err = String;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    #(
+                        if value.eq_ignore_ascii_case(#keys) {
+                            return Ok(Self::#variants);
+                        }
+                    )*
+
+                    Err(format!("unknown order `{value}`"))
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #order {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                    value.parse().map_err(::serde::de::Error::custom)
+                }
+            }
+
+            impl ::std::fmt::Display for #order {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    match self {
+                        #(Self::#variants => f.write_str(#keys),)*
+                    }
+                }
+            }
+
             #[derive(
                 Clone,
                 Copy,
-                ::serde::Deserialize,
                 ::serde::Serialize,
                 ::utoipa::ToSchema
             )]
@@ -144,8 +351,99 @@ impl PaginationRequest {
                 Desc,
             }
 
+            impl Default for #sort {
+                fn default() -> Self {
+                    Self::#default_sort
+                }
+            }
+
+            impl From<#sort> for ::lighter_common::prelude::sea_orm::Order {
+                fn from(value: #sort) -> Self {
+                    match value {
+                        #sort::Asc => Self::Asc,
+                        #sort::Desc => Self::Desc,
+                    }
+                }
+            }
+
+            impl ::std::str::FromStr for #sort {
+                type Err = String;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    match value.to_ascii_lowercase().as_str() {
+                        "asc" => Ok(Self::Asc),
+                        "desc" => Ok(Self::Desc),
+                        _ => Err(format!("unknown sort `{value}`")),
+                    }
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #sort {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                    value.parse().map_err(::serde::de::Error::custom)
+                }
+            }
+
+            impl ::std::fmt::Display for #sort {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    match self {
+                        Self::Asc => f.write_str("asc"),
+                        Self::Desc => f.write_str("desc"),
+                    }
+                }
+            }
+
+            #[derive(Clone)]
+            pub struct #sorts(pub Vec<(#order, #sort)>);
+
+            impl ::std::str::FromStr for #sorts {
+                type Err = String;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|entry| !entry.is_empty())
+                        .map(|entry| match entry.split_once(':') {
+                            Some((order, sort)) => Ok((order.trim().parse()?, sort.trim().parse()?)),
+                            None => Ok((entry.parse()?, #sort::default())),
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                        .map(Self)
+                }
+            }
+
+            impl ::std::fmt::Display for #sorts {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    let sorts = self
+                        .0
+                        .iter()
+                        .map(|(order, sort)| format!("{order}:{sort}"))
+                        .collect::<Vec<_>>();
+
+                    f.write_str(&sorts.join(","))
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #sorts {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = <String as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                    value.parse().map_err(::serde::de::Error::custom)
+                }
+            }
+
+            impl ::serde::Serialize for #sorts {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
             #[derive(
                 Clone,
+                Default,
                 ::serde::Deserialize,
                 ::serde::Serialize,
                 ::utoipa::ToSchema,
@@ -154,9 +452,11 @@ impl PaginationRequest {
             #[serde(rename_all = "camelCase")]
             #[into_params(parameter_in = Query)]
             pub struct #name {
-                #[schema(example = 1, required = false)]
+                #[schema(example = #example_page, minimum = #minimum, required = false)]
+                #[param(example = #example_page, minimum = #minimum)]
                 page: Option<u64>,
-                #[schema(example = 10)]
+                #[schema(example = #example_limit, minimum = #minimum, maximum = #maximum, required = false)]
+                #[param(example = #example_limit, minimum = #minimum, maximum = #maximum)]
                 limit: Option<u64>,
                 #[schema()]
                 search: Option<String>,
@@ -164,25 +464,45 @@ impl PaginationRequest {
                 sort: Option<#sort>,
                 #[schema()]
                 order: Option<#order>,
+                #[schema(value_type = Option<String>, example = "name:asc,createdAt:desc")]
+                #[param(value_type = Option<String>)]
+                sorts: Option<#sorts>,
             }
 
             impl #name {
+                pub fn new(page: u64, limit: u64) -> Self {
+                    Self {
+                        page: Some(page),
+                        limit: Some(limit),
+                        ..Default::default()
+                    }
+                }
+
                 pub fn page(&self) -> u64 {
-                    self.page.unwrap_or(1)
+                    self.page.unwrap_or(#default_page)
                 }
 
                 pub fn limit(&self) -> u64 {
-                    let limit = self.limit.unwrap_or(10);
+                    let limit = self.limit.unwrap_or(#default_limit);
 
-                    if limit > 1000 {
-                        1000
+                    if limit > #max_limit {
+                        #max_limit
                     } else {
                         limit
                     }
                 }
 
                 pub fn offset(&self) -> u64 {
-                    (self.page() - 1) * self.limit()
+                    self.page().saturating_sub(1).saturating_mul(self.limit())
+                }
+
+                pub fn validate(&self) -> Result<(), ::lighter_common::responses::Validation> {
+                    let mut validation = ::lighter_common::responses::Validation::new();
+
+                    validation.check(self.page != Some(0), "page", "Page must be at least 1");
+                    validation.check(self.limit != Some(0), "limit", "Limit must be at least 1");
+
+                    validation.into_result()
                 }
 
                 pub fn search(&self) -> Option<String> {
@@ -190,15 +510,75 @@ impl PaginationRequest {
                 }
 
                 pub fn sort(&self) -> ::lighter_common::prelude::sea_orm::Order {
-                    match self.sort.unwrap_or(#sort::Desc) {
-                        #sort::Asc => ::lighter_common::prelude::sea_orm::Order::Asc,
-                        #sort::Desc => ::lighter_common::prelude::sea_orm::Order::Desc,
-                    }
+                    self.sort.unwrap_or_default().into()
                 }
 
                 pub fn order(&self) -> #order {
                     self.order.clone().unwrap_or_default()
                 }
+
+                pub fn sorts(&self) -> Vec<(#order, ::lighter_common::prelude::sea_orm::Order)> {
+                    match &self.sorts {
+                        Some(sorts) if !sorts.0.is_empty() => sorts
+                            .0
+                            .iter()
+                            .map(|(order, sort)| (order.clone(), (*sort).into()))
+                            .collect(),
+                        _ => vec![(self.order(), self.sort())],
+                    }
+                }
+
+                pub fn search_condition<E>(&self) -> Option<::lighter_common::prelude::Condition>
+                where
+                    E: ::lighter_common::prelude::sea_orm::EntityTrait,
+                {
+                    use ::lighter_common::prelude::sea_orm::sea_query::{Expr, Func, LikeExpr};
+                    use ::lighter_common::prelude::sea_orm::{IdenStatic, Iterable};
+
+                    let searchables: &[&str] = &[#(#searchables),*];
+                    let search = self.search()?.to_lowercase();
+
+                    if search.is_empty() || searchables.is_empty() {
+                        return None;
+                    }
+
+                    let pattern = format!(
+                        "%{}%",
+                        search
+                            .replace('\\', "\\\\")
+                            .replace('%', "\\%")
+                            .replace('_', "\\_")
+                    );
+
+                    let condition = E::Column::iter()
+                        .filter(|column| searchables.contains(&column.as_str()))
+                        .fold(::lighter_common::prelude::Condition::any(), |condition, column| {
+                            condition.add(
+                                Expr::expr(Func::lower(Expr::col((E::default(), column))))
+                                    .like(LikeExpr::new(pattern.clone()).escape('\\')),
+                            )
+                        });
+
+                    Some(condition)
+                }
+
+                pub fn apply<E, F>(
+                    &self,
+                    query: ::lighter_common::prelude::sea_orm::Select<E>,
+                    column: F,
+                ) -> ::lighter_common::prelude::sea_orm::Select<E>
+                where
+                    E: ::lighter_common::prelude::sea_orm::EntityTrait,
+                    F: Fn(&#order) -> E::Column,
+                {
+                    use ::lighter_common::prelude::sea_orm::{QueryOrder, QuerySelect};
+
+                    self.sorts()
+                        .into_iter()
+                        .fold(query, |query, (order, sort)| query.order_by(column(&order), sort))
+                        .offset(self.offset())
+                        .limit(self.limit())
+                }
             }
         )
     }
@@ -222,6 +602,25 @@ fn capitalize<T: ToString>(input: T) -> String {
         .join("")
 }
 
+fn variant(literal: &LitStr, name: String) -> syn::Result<Ident> {
+    syn::parse_str::<Ident>(&name).map_err(|_| {
+        syn::Error::new_spanned(
+            literal,
+            format!("`{}` is not a valid variant name", literal.value()),
+        )
+    })
+}
+
+fn uncapitalize<T: ToString>(input: T) -> String {
+    let input = input.to_string();
+    let mut chars = input.chars();
+
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect::<String>(),
+        None => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,4 +648,45 @@ mod tests {
             "HelloWorldHelloWorld"
         );
     }
+
+    #[test]
+    fn test_invalid_rename() {
+        for rename in ["full-name", "2fa"] {
+            let input: DeriveInput = syn::parse_quote! {
+                struct User {
+                    #[order(rename = #rename)]
+                    name: String,
+                }
+            };
+            let error = PaginationRequest::new(input).err().unwrap();
+
+            assert_eq!(
+                error.to_string(),
+                format!("`{rename}` is not a valid variant name")
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_timestamp_field() {
+        let input: DeriveInput = syn::parse_quote! {
+            #[pagination(timestamp_field = "created-at")]
+            struct User {
+                name: String,
+            }
+        };
+        let error = PaginationRequest::new(input).err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "`created-at` is not a valid variant name"
+        );
+    }
+
+    #[test]
+    fn test_uncapitalize() {
+        assert_eq!(uncapitalize("CreatedAt"), "createdAt");
+        assert_eq!(uncapitalize("Name"), "name");
+        assert_eq!(uncapitalize(""), "");
+    }
 }
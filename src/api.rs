@@ -1,5 +1,10 @@
+use std::collections::BTreeMap;
+
 use utoipa::openapi;
-use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::security::{
+    ApiKey, ApiKeyValue, AuthorizationCode, Flow, HttpAuthScheme, HttpBuilder, OAuth2, Scopes,
+    SecurityScheme,
+};
 use utoipa::{Modify, OpenApi};
 
 use crate::responses;
@@ -12,7 +17,13 @@ use crate::responses;
     responses::Unauthorized,
     responses::Forbidden,
     responses::NotFound,
+    responses::Gone,
+    responses::PayloadTooLarge,
     responses::InternalServerError,
+    responses::NotImplemented,
+    responses::BadGateway,
+    responses::ServiceUnavailable,
+    responses::GatewayTimeout,
 )))]
 pub struct Builtin;
 
@@ -22,20 +33,171 @@ impl Modify for Builtin {
     }
 }
 
-pub struct Authentication;
+pub struct Authentication {
+    name: String,
+    bearer_format: Option<String>,
+}
+
+impl Authentication {
+    pub fn new<N: ToString>(name: N) -> Self {
+        Self {
+            name: name.to_string(),
+            bearer_format: None,
+        }
+    }
+
+    pub fn bearer_format<F: ToString>(mut self, bearer_format: F) -> Self {
+        self.bearer_format = Some(bearer_format.to_string());
+        self
+    }
+}
+
+impl Default for Authentication {
+    fn default() -> Self {
+        Self::new("token").bearer_format("JWT")
+    }
+}
 
 impl Modify for Authentication {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            let mut scheme = HttpBuilder::new().scheme(HttpAuthScheme::Bearer);
+
+            if let Some(bearer_format) = &self.bearer_format {
+                scheme = scheme.bearer_format(bearer_format);
+            }
+
+            components.add_security_scheme(&self.name, SecurityScheme::Http(scheme.build()))
+        }
+    }
+}
+
+pub struct ApiKeyAuthentication;
+
+impl Modify for ApiKeyAuthentication {
     fn modify(&self, openapi: &mut openapi::OpenApi) {
         if let Some(components) = openapi.components.as_mut() {
             components.add_security_scheme(
-                "token",
-                SecurityScheme::Http(
-                    HttpBuilder::new()
-                        .scheme(HttpAuthScheme::Bearer)
-                        .bearer_format("JWT")
-                        .build(),
-                ),
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
             )
         }
     }
 }
+
+pub struct OAuth2Authentication {
+    name: String,
+    authorization_url: String,
+    token_url: String,
+    scopes: BTreeMap<String, String>,
+}
+
+impl OAuth2Authentication {
+    pub fn new<A: ToString, T: ToString>(authorization_url: A, token_url: T) -> Self {
+        Self {
+            name: "oauth2".to_string(),
+            authorization_url: authorization_url.to_string(),
+            token_url: token_url.to_string(),
+            scopes: BTreeMap::new(),
+        }
+    }
+
+    pub fn name<N: ToString>(mut self, name: N) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn scope<S: ToString, D: ToString>(mut self, scope: S, description: D) -> Self {
+        self.scopes
+            .insert(scope.to_string(), description.to_string());
+        self
+    }
+}
+
+impl Modify for OAuth2Authentication {
+    fn modify(&self, openapi: &mut openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            let scopes = self
+                .scopes
+                .iter()
+                .map(|(scope, description)| (scope.as_str(), description.as_str()))
+                .collect::<Scopes>();
+
+            components.add_security_scheme(
+                &self.name,
+                SecurityScheme::OAuth2(OAuth2::new([Flow::AuthorizationCode(
+                    AuthorizationCode::new(&self.authorization_url, &self.token_url, scopes),
+                )])),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn bearer() {
+        let mut openapi = Builtin::openapi();
+
+        Authentication::default().modify(&mut openapi);
+        Authentication::new("session")
+            .bearer_format("opaque")
+            .modify(&mut openapi);
+
+        let openapi = serde_json::to_value(openapi).unwrap();
+        let schemes = &openapi["components"]["securitySchemes"];
+
+        assert_eq!(
+            schemes["token"],
+            json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" })
+        );
+        assert_eq!(
+            schemes["session"],
+            json!({ "type": "http", "scheme": "bearer", "bearerFormat": "opaque" })
+        );
+    }
+
+    #[test]
+    fn api_key() {
+        #[derive(OpenApi)]
+        #[openapi(modifiers(&Builtin, &ApiKeyAuthentication))]
+        struct Doc;
+
+        let openapi = serde_json::to_value(Doc::openapi()).unwrap();
+
+        assert_eq!(
+            openapi["components"]["securitySchemes"]["api_key"],
+            json!({ "type": "apiKey", "in": "header", "name": "X-API-Key" })
+        );
+    }
+
+    #[test]
+    fn oauth2() {
+        let mut openapi = Builtin::openapi();
+
+        OAuth2Authentication::new(
+            "https://auth.example.com/authorize",
+            "https://auth.example.com/token",
+        )
+        .scope("users:read", "Read users")
+        .scope("users:write", "Modify users")
+        .modify(&mut openapi);
+
+        let openapi = serde_json::to_value(openapi).unwrap();
+        let flow =
+            &openapi["components"]["securitySchemes"]["oauth2"]["flows"]["authorizationCode"];
+
+        assert_eq!(
+            flow["authorizationUrl"],
+            "https://auth.example.com/authorize"
+        );
+        assert_eq!(flow["tokenUrl"], "https://auth.example.com/token");
+        assert_eq!(
+            flow["scopes"],
+            json!({ "users:read": "Read users", "users:write": "Modify users" })
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use std::future::{ready, Ready};
+use std::ops::Deref;
+
+use actix_web::dev::Payload;
+use actix_web::http::header::{HeaderMap, AUTHORIZATION};
+use actix_web::{FromRequest, HttpRequest};
+
+use crate::responses::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BearerToken(pub String);
+
+impl BearerToken {
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, Error> {
+        let header = headers
+            .get(AUTHORIZATION)
+            .ok_or_else(|| Error::Unauthorized {
+                message: "Missing token".to_string(),
+            })?;
+
+        header
+            .to_str()
+            .ok()
+            .and_then(|value| value.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+            .map(|(_, token)| token.trim())
+            .filter(|token| !token.is_empty())
+            .map(|token| Self(token.to_string()))
+            .ok_or_else(|| Error::Unauthorized {
+                message: "Invalid authorization header".to_string(),
+            })
+    }
+}
+
+impl Deref for BearerToken {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromRequest for BearerToken {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(BearerToken::from_headers(request.headers()))
+    }
+}
+
+pub trait Permissions {
+    fn has_permission(&self, permission: &str) -> bool;
+}
+
+pub fn require<T: Permissions + ?Sized>(principal: &T, permission: &str) -> Result<(), Error> {
+    if principal.has_permission(permission) {
+        Ok(())
+    } else {
+        Err(Error::Forbidden {
+            message: format!("Missing permission `{permission}`"),
+        })
+    }
+}
+
+#[cfg(feature = "jsonwebtoken")]
+pub use self::claims::*;
+
+#[cfg(feature = "jsonwebtoken")]
+mod claims {
+    use std::future::{ready, Ready};
+    use std::ops::Deref;
+
+    use actix_web::dev::Payload;
+    use actix_web::web::Data;
+    use actix_web::{FromRequest, HttpRequest};
+    use serde::de::DeserializeOwned;
+
+    use super::{BearerToken, Permissions};
+    use crate::jwt;
+    use crate::responses::Error;
+
+    #[derive(Clone)]
+    pub struct Secret(pub String);
+
+    #[derive(Clone, Debug)]
+    pub struct Claims<T>(pub T);
+
+    impl<T> Claims<T> {
+        pub fn into_inner(self) -> T {
+            self.0
+        }
+    }
+
+    impl<T: Permissions> Claims<T> {
+        pub fn require(&self, permission: &str) -> Result<(), Error> {
+            super::require(&self.0, permission)
+        }
+    }
+
+    impl<T> Deref for Claims<T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            &self.0
+        }
+    }
+
+    impl<T: DeserializeOwned> FromRequest for Claims<T> {
+        type Error = Error;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+            let claims = || {
+                let token = BearerToken::from_headers(request.headers())?;
+                let secret = request.app_data::<Data<Secret>>().ok_or_else(|| {
+                    Error::InternalServerError {
+                        message: "JWT secret is not configured".to_string(),
+                    }
+                })?;
+
+                jwt::decode(&token, &secret.0).map(Self)
+            };
+
+            ready(claims())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[actix_web::test]
+    async fn present() {
+        let request = TestRequest::default()
+            .insert_header((AUTHORIZATION, "Bearer abc.def.ghi"))
+            .to_http_request();
+        let token = BearerToken::extract(&request).await.unwrap();
+
+        assert_eq!(&*token, "abc.def.ghi");
+        assert_eq!(BearerToken::from_headers(request.headers()), Ok(token));
+
+        for header in ["bearer abc", "BEARER abc"] {
+            let request = TestRequest::default()
+                .insert_header((AUTHORIZATION, header))
+                .to_http_request();
+            let token = BearerToken::extract(&request).await.unwrap();
+
+            assert_eq!(&*token, "abc");
+        }
+    }
+
+    #[actix_web::test]
+    async fn missing() {
+        let request = TestRequest::default().to_http_request();
+        let error = BearerToken::extract(&request).await.unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Unauthorized {
+                message: "Missing token".to_string()
+            }
+        );
+    }
+
+    #[actix_web::test]
+    async fn malformed() {
+        for header in ["Basic abc", "Bearer ", "Bearerabc"] {
+            let request = TestRequest::default()
+                .insert_header((AUTHORIZATION, header))
+                .to_http_request();
+            let error = BearerToken::extract(&request).await.unwrap_err();
+
+            assert_eq!(
+                error,
+                Error::Unauthorized {
+                    message: "Invalid authorization header".to_string()
+                }
+            );
+        }
+    }
+
+    #[cfg(feature = "jsonwebtoken")]
+    #[actix_web::test]
+    async fn claims() {
+        use std::time::Duration;
+
+        use actix_web::web::Data;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Deserialize, Serialize)]
+        struct User {
+            sub: String,
+            permissions: Vec<String>,
+        }
+
+        impl Permissions for User {
+            fn has_permission(&self, permission: &str) -> bool {
+                self.permissions.iter().any(|p| p == permission)
+            }
+        }
+
+        let user = User {
+            sub: "john".to_string(),
+            permissions: vec!["user.read".to_string()],
+        };
+        let token = crate::jwt::encode(&user, "secret", Duration::from_secs(60)).unwrap();
+        let request = TestRequest::default()
+            .app_data(Data::new(Secret("secret".to_string())))
+            .insert_header((AUTHORIZATION, format!("Bearer {token}")))
+            .to_http_request();
+        let claims = Claims::<User>::extract(&request).await.unwrap();
+
+        assert_eq!(claims.sub, "john");
+        assert!(claims.require("user.read").is_ok());
+        assert_eq!(
+            claims.require("user.delete").unwrap_err().status_code(),
+            403
+        );
+
+        let request = TestRequest::default()
+            .app_data(Data::new(Secret("other".to_string())))
+            .insert_header((AUTHORIZATION, format!("Bearer {token}")))
+            .to_http_request();
+        let error = Claims::<User>::extract(&request).await.unwrap_err();
+
+        assert_eq!(error.status_code(), 401);
+    }
+
+    #[cfg(feature = "jsonwebtoken")]
+    #[actix_web::test]
+    async fn claims_errors() {
+        use actix_web::web::Data;
+        use jsonwebtoken::{get_current_timestamp, EncodingKey, Header};
+        use serde::Deserialize;
+        use serde_json::json;
+
+        #[derive(Debug, Deserialize)]
+        struct User {
+            #[allow(dead_code)]
+            sub: String,
+        }
+
+        let now = get_current_timestamp();
+        let expired = jsonwebtoken::encode(
+            &Header::default(),
+            &json!({ "sub": "john", "iat": now - 600, "exp": now - 300 }),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        let cases = [
+            (None, "Missing token"),
+            (
+                Some("Token abc".to_string()),
+                "Invalid authorization header",
+            ),
+            (Some("Bearer abc".to_string()), "Invalid token"),
+            (Some(format!("Bearer {expired}")), "Token has expired"),
+        ];
+
+        for (header, message) in cases {
+            let mut request =
+                TestRequest::default().app_data(Data::new(Secret("secret".to_string())));
+
+            if let Some(header) = header {
+                request = request.insert_header((AUTHORIZATION, header));
+            }
+
+            let error = Claims::<User>::extract(&request.to_http_request())
+                .await
+                .unwrap_err();
+
+            assert_eq!(
+                error,
+                Error::Unauthorized {
+                    message: message.to_string()
+                }
+            );
+        }
+    }
+
+    struct Admin;
+
+    impl Permissions for Admin {
+        fn has_permission(&self, _: &str) -> bool {
+            true
+        }
+    }
+
+    struct Guest;
+
+    impl Permissions for Guest {
+        fn has_permission(&self, _: &str) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn permissions() {
+        assert!(require(&Admin, "user.delete").is_ok());
+        assert_eq!(
+            require(&Guest, "user.delete").unwrap_err(),
+            Error::Forbidden {
+                message: "Missing permission `user.delete`".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::base58;
+
+pub fn encode<T: Serialize>(key: &T) -> Result<String, String> {
+    let bytes = serde_json::to_vec(key).map_err(|e| e.to_string())?;
+
+    Ok(base58::to_string(bytes))
+}
+
+pub fn decode<T: DeserializeOwned, S: AsRef<[u8]>>(cursor: S) -> Result<T, String> {
+    let bytes = base58::decode(cursor).map_err(|e| e.to_string())?;
+
+    serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn round_trip() {
+        let key = (1706745600000i64, Uuid::new_v4());
+        let cursor = encode(&key).unwrap();
+
+        assert_eq!(decode::<(i64, Uuid), _>(cursor), Ok(key));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(decode::<i64, _>("0OIl").is_err());
+        assert!(decode::<i64, _>(base58::to_string("not json")).is_err());
+    }
+
+    #[test]
+    fn unserializable() {
+        use std::collections::HashMap;
+
+        let key = HashMap::from([((1, 2), "tuple keys are not valid json")]);
+
+        assert!(encode(&key).is_err());
+    }
+}
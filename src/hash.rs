@@ -1,7 +1,19 @@
 use std::fmt;
+#[cfg(feature = "web")]
+use std::future::{ready, Ready};
+use std::str::FromStr;
 
+#[cfg(feature = "web")]
+use actix_web::dev::Payload;
+#[cfg(feature = "web")]
+use actix_web::{FromRequest, HttpRequest};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use sha2::{Digest, Sha256};
 
+use crate::base58;
+#[cfg(feature = "web")]
+use crate::responses::Error;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Hash(pub [u8; 32]);
 
@@ -11,12 +23,19 @@ impl fmt::Display for Hash {
     }
 }
 
-impl From<Vec<u8>> for Hash {
-    fn from(value: Vec<u8>) -> Self {
-        let mut digest = [0u8; 32];
-        digest.copy_from_slice(&value);
+impl TryFrom<Vec<u8>> for Hash {
+    type Error = String;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_slice(&value)
+    }
+}
+
+impl TryFrom<&[u8]> for Hash {
+    type Error = String;
 
-        Self::from(digest)
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_slice(value)
     }
 }
 
@@ -26,25 +45,68 @@ impl From<[u8; 32]> for Hash {
     }
 }
 
-impl From<String> for Hash {
-    fn from(value: String) -> Self {
-        Self::from(hex::decode(value).unwrap())
+impl TryFrom<String> for Hash {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<&String> for Hash {
+    type Error = String;
+
+    fn try_from(value: &String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<&str> for Hash {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl FromStr for Hash {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(value).map_err(|e| e.to_string())?;
+
+        Self::from_slice(&bytes)
     }
 }
 
-impl From<&String> for Hash {
-    fn from(value: &String) -> Self {
-        Self::from(hex::decode(value).unwrap())
+impl<'de> Deserialize<'de> for Hash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(serde::de::Error::custom)
     }
 }
 
-impl From<&str> for Hash {
-    fn from(value: &str) -> Self {
-        Self::from(value.to_string())
+impl Serialize for Hash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
     }
 }
 
 impl Hash {
+    pub const LEN: usize = 32;
+
+    pub fn from_slice(value: &[u8]) -> Result<Self, String> {
+        if value.len() != Self::LEN {
+            return Err(format!("expected {} bytes, got {}", Self::LEN, value.len()));
+        }
+
+        let mut digest = [0u8; Self::LEN];
+        digest.copy_from_slice(value);
+
+        Ok(Self(digest))
+    }
+
     pub fn make<S, M>(salt: S, message: M) -> Self
     where
         S: AsRef<[u8]>,
@@ -54,7 +116,15 @@ impl Hash {
         hasher.update(salt);
         hasher.update(message);
 
-        Self::from(hasher.finalize().to_vec())
+        Self(hasher.finalize().into())
+    }
+
+    pub fn token<S, M>(salt: S, message: M) -> String
+    where
+        S: AsRef<[u8]>,
+        M: AsRef<[u8]>,
+    {
+        base58::to_string(Self::make(salt, message).0)
     }
 
     pub fn verify<S, M>(&self, salt: S, message: M) -> bool
@@ -66,6 +136,75 @@ impl Hash {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResourceId(pub Hash);
+
+#[cfg(feature = "web")]
+impl ResourceId {
+    pub fn from_path(request: &HttpRequest) -> Result<Self, Error> {
+        Self::from_param(request, "id")
+    }
+
+    pub fn from_param(request: &HttpRequest, name: &str) -> Result<Self, Error> {
+        let value = request
+            .match_info()
+            .get(name)
+            .ok_or_else(|| Error::BadRequest {
+                message: format!("Missing resource id `{}`", name),
+            })?;
+
+        value.parse().map_err(|e| Error::BadRequest {
+            message: format!("Invalid resource id: {}", e),
+        })
+    }
+}
+
+impl fmt::Display for ResourceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&base58::to_string(self.0 .0))
+    }
+}
+
+impl From<Hash> for ResourceId {
+    fn from(value: Hash) -> Self {
+        Self(value)
+    }
+}
+
+impl FromStr for ResourceId {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bytes = base58::decode(value).map_err(|e| e.to_string())?;
+
+        Hash::from_slice(&bytes).map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResourceId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for ResourceId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "web")]
+impl FromRequest for ResourceId {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(request: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(ResourceId::from_path(request))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -88,4 +227,124 @@ mod test {
 
         assert!(!hash.verify(salt, "Hello World"));
     }
+
+    #[test]
+    fn parse() {
+        let hash = Hash::make("salt", "message");
+        let parsed = hash.to_string().parse::<Hash>();
+
+        assert_eq!(parsed, Ok(hash));
+        assert!("not-hex".parse::<Hash>().is_err());
+        assert!("abcd".parse::<Hash>().is_err());
+        assert_eq!(Hash::try_from(hash.to_string()), Ok(hash));
+        assert_eq!(Hash::try_from(&hash.to_string()), Ok(hash));
+        assert_eq!(
+            Hash::try_from("zz"),
+            Err("Invalid character 'z' at position 0".to_string())
+        );
+    }
+
+    #[test]
+    fn token() {
+        let token = Hash::token("salt", "secret");
+
+        assert_eq!(token, Hash::token("salt", "secret"));
+        assert_ne!(token, Hash::token("salt", "other"));
+        assert_ne!(token, Hash::token("pepper", "secret"));
+        assert_eq!(
+            base58::decode(&token).unwrap(),
+            Hash::make("salt", "secret").0
+        );
+    }
+
+    #[test]
+    fn length() {
+        assert_eq!(
+            Hash::from_slice(&[0u8; 31]),
+            Err("expected 32 bytes, got 31".to_string())
+        );
+        assert_eq!(
+            Hash::from_slice(&[0u8; 33]),
+            Err("expected 32 bytes, got 33".to_string())
+        );
+        assert_eq!(
+            hex::encode([0u8; 31]).parse::<Hash>(),
+            Err("expected 32 bytes, got 31".to_string())
+        );
+        assert!(Hash::from_slice(&[0u8; Hash::LEN]).is_ok());
+        assert_eq!(
+            Hash::try_from(vec![0u8; 31]),
+            Err("expected 32 bytes, got 31".to_string())
+        );
+        assert_eq!(Hash::try_from(&[1u8; 32][..]), Ok(Hash([1u8; 32])));
+    }
+
+    #[cfg(feature = "web")]
+    #[actix_web::test]
+    async fn path() {
+        use actix_web::test::TestRequest;
+        use actix_web::web::Path;
+
+        let hash = Hash::make("salt", "message");
+        let request = TestRequest::default()
+            .param("hash", hash.to_string())
+            .to_http_request();
+        let path = Path::<Hash>::extract(&request).await.unwrap();
+
+        assert_eq!(*path, hash);
+
+        let request = TestRequest::default().param("hash", "zz").to_http_request();
+
+        assert!(Path::<Hash>::extract(&request).await.is_err());
+    }
+
+    #[cfg(feature = "web")]
+    #[actix_web::test]
+    async fn resource_id() {
+        use actix_web::test::TestRequest;
+
+        let hash = Hash::make("salt", "message");
+        let id = ResourceId::from(hash);
+
+        assert_eq!(id.to_string(), Hash::token("salt", "message"));
+        assert_eq!(id.to_string().parse::<ResourceId>(), Ok(id));
+
+        let request = TestRequest::default()
+            .param("id", id.to_string())
+            .to_http_request();
+        let extracted = ResourceId::extract(&request).await.unwrap();
+
+        assert_eq!(extracted.0, hash);
+
+        let request = TestRequest::default()
+            .param("id", hash.to_string())
+            .to_http_request();
+        let error = ResourceId::extract(&request).await.unwrap_err();
+
+        assert_eq!(error.status_code(), 400);
+
+        let request = TestRequest::default().param("id", "0OIl").to_http_request();
+
+        assert!(ResourceId::extract(&request).await.is_err());
+
+        let request = TestRequest::default()
+            .param("org", id.to_string())
+            .to_http_request();
+        let error = ResourceId::extract(&request).await.unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::BadRequest {
+                message: "Missing resource id `id`".to_string()
+            }
+        );
+
+        let request = TestRequest::default()
+            .param("org", Hash::token("salt", "org"))
+            .param("user", id.to_string())
+            .to_http_request();
+
+        assert_eq!(ResourceId::from_param(&request, "user"), Ok(id));
+        assert!(ResourceId::from_path(&request).is_err());
+    }
 }
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix::clock::timeout;
+use actix_web::web::{self, Data, ServiceConfig};
+use actix_web::HttpResponse;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+
+pub type Check<'a> = Pin<Box<dyn Future<Output = Result<(), String>> + 'a>>;
+
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> String;
+
+    fn check(&self) -> Check<'_>;
+}
+
+impl HealthCheck for DatabaseConnection {
+    fn name(&self) -> String {
+        "database".to_string()
+    }
+
+    fn check(&self) -> Check<'_> {
+        Box::pin(async move { self.ping().await.map_err(|e| e.to_string()) })
+    }
+}
+
+#[derive(Clone)]
+pub struct Health {
+    checks: Vec<Arc<dyn HealthCheck>>,
+    timeout: Duration,
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self {
+            checks: vec![],
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    pub fn add<C: HealthCheck + 'static>(&mut self, check: C) {
+        self.checks.push(Arc::new(check));
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn configure(&self, config: &mut ServiceConfig) {
+        config
+            .app_data(Data::new(self.clone()))
+            .route("/health", web::get().to(liveness))
+            .route("/ready", web::get().to(readiness));
+    }
+
+    pub async fn run(&self) -> BTreeMap<String, Result<(), String>> {
+        let handles = self
+            .checks
+            .iter()
+            .map(|check| {
+                let name = check.name();
+                let check = check.clone();
+                let duration = self.timeout;
+                let handle = actix::spawn(async move {
+                    match timeout(duration, check.check()).await {
+                        Ok(result) => result,
+                        Err(_) => Err(format!("Timed out after {}ms", duration.as_millis())),
+                    }
+                });
+
+                (name, handle)
+            })
+            .collect::<Vec<_>>();
+
+        let mut results = BTreeMap::new();
+
+        for (name, handle) in handles {
+            let result = handle.await.unwrap_or_else(|e| Err(e.to_string()));
+
+            match results.get(&name) {
+                Some(Err(_)) => {}
+                _ => {
+                    results.insert(name, result);
+                }
+            }
+        }
+
+        results
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub async fn liveness() -> HttpResponse {
+    HttpResponse::Ok().json(json!({ "status": "ok" }))
+}
+
+pub async fn readiness(health: Data<Health>) -> HttpResponse {
+    let results = health.run().await;
+    let healthy = results.values().all(Result::is_ok);
+    let checks = results
+        .into_iter()
+        .map(|(name, result)| (name, result.err().unwrap_or_else(|| "ok".to_string())))
+        .collect::<BTreeMap<_, _>>();
+
+    if healthy {
+        HttpResponse::Ok().json(json!({ "status": "ok", "checks": checks }))
+    } else {
+        HttpResponse::ServiceUnavailable()
+            .json(json!({ "status": "unavailable", "checks": checks }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::{web, App};
+    use serde_json::Value;
+
+    struct Passing;
+
+    impl HealthCheck for Passing {
+        fn name(&self) -> String {
+            "queue".to_string()
+        }
+
+        fn check(&self) -> Check<'_> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    struct Failing;
+
+    impl HealthCheck for Failing {
+        fn name(&self) -> String {
+            "payments".to_string()
+        }
+
+        fn check(&self) -> Check<'_> {
+            Box::pin(async { Err("Connection refused".to_string()) })
+        }
+    }
+
+    struct Slow;
+
+    impl HealthCheck for Slow {
+        fn name(&self) -> String {
+            "search".to_string()
+        }
+
+        fn check(&self) -> Check<'_> {
+            Box::pin(async {
+                actix::clock::sleep(Duration::from_secs(1)).await;
+
+                Ok(())
+            })
+        }
+    }
+
+    #[actix_web::test]
+    async fn readiness() {
+        let mut health = Health::new();
+
+        health.add(Passing);
+
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(health.clone()))
+                .route("/ready", web::get().to(super::readiness)),
+        )
+        .await;
+        let request = TestRequest::get().uri("/ready").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 200);
+
+        health.add(Failing);
+        health.add(Slow);
+        health.timeout(Duration::from_millis(50));
+
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(health))
+                .route("/ready", web::get().to(super::readiness)),
+        )
+        .await;
+        let request = TestRequest::get().uri("/ready").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 503);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(
+            body,
+            json!({
+                "status": "unavailable",
+                "checks": {
+                    "queue": "ok",
+                    "payments": "Connection refused",
+                    "search": "Timed out after 50ms",
+                },
+            })
+        );
+    }
+
+    #[actix_web::test]
+    async fn database() {
+        let mut health = Health::new();
+
+        health.add(DatabaseConnection::Disconnected);
+
+        let results = health.run().await;
+
+        assert!(results["database"].is_err());
+    }
+
+    #[actix_web::test]
+    async fn collision() {
+        struct Named(&'static str, Result<(), String>);
+
+        impl HealthCheck for Named {
+            fn name(&self) -> String {
+                self.0.to_string()
+            }
+
+            fn check(&self) -> Check<'_> {
+                Box::pin(async { self.1.clone() })
+            }
+        }
+
+        let mut health = Health::new();
+
+        health.add(Named("database", Err("Connection refused".to_string())));
+        health.add(Named("database", Ok(())));
+
+        assert_eq!(
+            health.run().await["database"],
+            Err("Connection refused".to_string())
+        );
+
+        let mut health = Health::new();
+
+        health.add(Named("database", Ok(())));
+        health.add(Named("database", Err("Connection refused".to_string())));
+
+        assert_eq!(
+            health.run().await["database"],
+            Err("Connection refused".to_string())
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{get_current_timestamp, DecodingKey, EncodingKey, Header, Validation};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::responses::Error;
+
+#[derive(Deserialize, Serialize)]
+struct Token<T> {
+    #[serde(flatten)]
+    claims: T,
+    iat: u64,
+    exp: u64,
+}
+
+pub fn encode<T: Serialize, S: AsRef<[u8]>>(
+    claims: &T,
+    secret: S,
+    ttl: Duration,
+) -> Result<String, Error> {
+    let failed = || Error::InternalServerError {
+        message: "Failed to encode token".to_string(),
+    };
+    let claims = serde_json::to_value(claims).map_err(|e| failed().with_cause(e))?;
+
+    // iat and exp are set here, a claims type carrying them would be serialized twice
+    if claims.get("iat").is_some() || claims.get("exp").is_some() {
+        return Err(failed().with_cause("claims must not contain `iat` or `exp`"));
+    }
+
+    let now = get_current_timestamp();
+    let token = Token {
+        claims,
+        iat: now,
+        exp: now + ttl.as_secs(),
+    };
+    let key = EncodingKey::from_secret(secret.as_ref());
+
+    jsonwebtoken::encode(&Header::default(), &token, &key).map_err(|e| failed().with_cause(e))
+}
+
+pub fn decode<T: DeserializeOwned, S: AsRef<[u8]>>(token: &str, secret: S) -> Result<T, Error> {
+    let key = DecodingKey::from_secret(secret.as_ref());
+
+    match jsonwebtoken::decode::<Token<T>>(token, &key, &Validation::default()) {
+        Ok(data) => Ok(data.claims.claims),
+        Err(e) => {
+            let message = match e.kind() {
+                ErrorKind::ExpiredSignature => "Token has expired",
+                ErrorKind::InvalidSignature => "Invalid token signature",
+                _ => "Invalid token",
+            };
+
+            Err(Error::Unauthorized {
+                message: message.to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Claims {
+        sub: String,
+    }
+
+    fn claims() -> Claims {
+        Claims {
+            sub: "john".to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let token = encode(&claims(), "secret", Duration::from_secs(60)).unwrap();
+        let decoded: Claims = decode(&token, "secret").unwrap();
+
+        assert_eq!(decoded, claims());
+    }
+
+    #[test]
+    fn reserved_claims() {
+        #[derive(Serialize)]
+        struct Session {
+            sub: String,
+            exp: u64,
+        }
+
+        let session = Session {
+            sub: "john".to_string(),
+            exp: 0,
+        };
+        let error = encode(&session, "secret", Duration::from_secs(60)).unwrap_err();
+
+        assert_eq!(error.status_code(), 500);
+        assert_eq!(error.message(), Some("Failed to encode token"));
+        assert_eq!(
+            error.cause(),
+            Some("claims must not contain `iat` or `exp`")
+        );
+    }
+
+    #[test]
+    fn invalid_signature() {
+        let token = encode(&claims(), "secret", Duration::from_secs(60)).unwrap();
+        let error = decode::<Claims, _>(&token, "other").unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Unauthorized {
+                message: "Invalid token signature".to_string()
+            }
+        );
+        assert!(decode::<Claims, _>("not-a-token", "secret").is_err());
+    }
+
+    #[test]
+    fn expired() {
+        let now = get_current_timestamp();
+        let token = Token {
+            claims: claims(),
+            iat: now - 600,
+            exp: now - 300,
+        };
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &token,
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        let error = decode::<Claims, _>(&token, "secret").unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::Unauthorized {
+                message: "Token has expired".to_string()
+            }
+        );
+    }
+}
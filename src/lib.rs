@@ -1,10 +1,27 @@
+#[cfg(feature = "web")]
 pub mod api;
+#[cfg(feature = "web")]
+pub mod auth;
 pub mod base58;
+pub mod cursor;
+#[cfg(feature = "db")]
 pub mod database;
 pub mod hash;
+#[cfg(feature = "web")]
+pub mod health;
+#[cfg(feature = "jsonwebtoken")]
+pub mod jwt;
+#[cfg(feature = "web")]
+pub mod middleware;
 pub mod prelude;
+#[cfg(feature = "web")]
 pub mod responses;
+#[cfg(feature = "web")]
+pub mod routes;
+#[cfg(feature = "web")]
 pub mod server;
 pub mod time;
+#[cfg(feature = "web")]
 pub mod tls;
+#[cfg(feature = "tracing")]
 pub mod tracing;
@@ -0,0 +1,132 @@
+use std::any::Any;
+use std::future::{ready, Future, Ready};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::Error;
+
+use crate::responses;
+
+#[derive(Clone, Default)]
+pub struct CatchPanic;
+
+impl<S, B> Transform<S, ServiceRequest> for CatchPanic
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = CatchPanicMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CatchPanicMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct CatchPanicMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for CatchPanicMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let result = CatchUnwind(Box::pin(async move { service.call(request).await })).await;
+
+            result.unwrap_or_else(|panic| {
+                let error = responses::Error::InternalServerError {
+                    message: "Internal server error".to_string(),
+                }
+                .with_cause(format!("handler panicked: {}", message(&*panic)));
+
+                Err(error.into())
+            })
+        })
+    }
+}
+
+struct CatchUnwind<F: Future>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.0.as_mut();
+
+        match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}
+
+fn message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::test::{call_service, init_service, try_call_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+    use serde_json::Value;
+
+    async fn panics() -> HttpResponse {
+        panic!("boom")
+    }
+
+    #[actix_web::test]
+    async fn panic() {
+        let app = init_service(
+            App::new()
+                .wrap(CatchPanic)
+                .route("/ok", web::get().to(HttpResponse::Ok))
+                .route("/panic", web::get().to(panics)),
+        )
+        .await;
+        let response = call_service(&app, TestRequest::get().uri("/ok").to_request()).await;
+
+        assert_eq!(response.status(), 200);
+
+        let error = try_call_service(&app, TestRequest::get().uri("/panic").to_request())
+            .await
+            .err()
+            .unwrap();
+        let response = error.error_response();
+
+        assert_eq!(response.status(), 500);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body["message"], "Internal server error");
+        assert!(body["error_id"].is_string());
+    }
+}
@@ -0,0 +1,290 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::ContentEncoding;
+use actix_web::Error;
+
+#[cfg(any(
+    feature = "compress-brotli",
+    feature = "compress-gzip",
+    feature = "compress-zstd"
+))]
+pub use self::enabled::*;
+
+#[derive(Clone, Debug)]
+pub struct Compression {
+    min_size: usize,
+    algorithms: Vec<ContentEncoding>,
+}
+
+impl Compression {
+    pub fn new() -> Self {
+        Self {
+            min_size: 1024,
+            algorithms: Self::supported(),
+        }
+    }
+
+    pub fn min_size(mut self, bytes: usize) -> Self {
+        self.min_size = bytes;
+        self
+    }
+
+    pub fn algorithms<A: IntoIterator<Item = ContentEncoding>>(mut self, algorithms: A) -> Self {
+        let supported = Self::supported();
+
+        self.algorithms = algorithms
+            .into_iter()
+            .filter(|algorithm| supported.contains(algorithm))
+            .collect();
+        self
+    }
+
+    pub fn supported() -> Vec<ContentEncoding> {
+        vec![
+            #[cfg(feature = "compress-brotli")]
+            ContentEncoding::Brotli,
+            #[cfg(feature = "compress-gzip")]
+            ContentEncoding::Gzip,
+            #[cfg(feature = "compress-gzip")]
+            ContentEncoding::Deflate,
+            #[cfg(feature = "compress-zstd")]
+            ContentEncoding::Zstd,
+        ]
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(any(
+    feature = "compress-brotli",
+    feature = "compress-gzip",
+    feature = "compress-zstd"
+)))]
+impl<S, B> Transform<S, ServiceRequest> for Compression
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = S;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(service))
+    }
+}
+
+#[cfg(any(
+    feature = "compress-brotli",
+    feature = "compress-gzip",
+    feature = "compress-zstd"
+))]
+mod enabled {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::rc::Rc;
+
+    use actix_web::body::{BodySize, MessageBody};
+    use actix_web::dev::forward_ready;
+    use actix_web::http::header::{HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING};
+    use actix_web::middleware::Compress;
+
+    use super::*;
+
+    type Inner<S> = <Compress as Transform<MinSize<S>, ServiceRequest>>::Transform;
+
+    impl Compression {
+        fn accept(&self, header: &str) -> String {
+            let mut accepted = vec![];
+
+            for item in header.split(',').map(str::trim) {
+                let (coding, params) = item.split_once(';').unwrap_or((item, ""));
+                let coding = coding.trim();
+
+                if coding == "*" {
+                    for algorithm in &self.algorithms {
+                        accepted.push(match params.trim() {
+                            "" => algorithm.as_str().to_string(),
+                            params => format!("{};{}", algorithm.as_str(), params),
+                        });
+                    }
+                } else if coding.eq_ignore_ascii_case("identity")
+                    || self
+                        .algorithms
+                        .iter()
+                        .any(|algorithm| coding.eq_ignore_ascii_case(algorithm.as_str()))
+                {
+                    accepted.push(item.to_string());
+                }
+            }
+
+            match accepted.is_empty() {
+                true => "identity".to_string(),
+                false => accepted.join(", "),
+            }
+        }
+    }
+
+    impl<S, B> Transform<S, ServiceRequest> for Compression
+    where
+        S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: MessageBody + 'static,
+    {
+        type Response = <Inner<S> as Service<ServiceRequest>>::Response;
+        type Error = Error;
+        type Transform = CompressionMiddleware<Inner<S>>;
+        type InitError = ();
+        type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+        fn new_transform(&self, service: S) -> Self::Future {
+            let service = MinSize {
+                service: Rc::new(service),
+                min_size: self.min_size as u64,
+            };
+            let middleware =
+                Compress::default()
+                    .new_transform(service)
+                    .into_inner()
+                    .map(|service| CompressionMiddleware {
+                        service,
+                        compression: self.clone(),
+                    });
+
+            ready(middleware)
+        }
+    }
+
+    pub struct CompressionMiddleware<S> {
+        service: S,
+        compression: Compression,
+    }
+
+    impl<S> Service<ServiceRequest> for CompressionMiddleware<S>
+    where
+        S: Service<ServiceRequest, Error = Error>,
+    {
+        type Response = S::Response;
+        type Error = Error;
+        type Future = S::Future;
+
+        forward_ready!(service);
+
+        fn call(&self, mut request: ServiceRequest) -> Self::Future {
+            let accept = request
+                .headers()
+                .get(ACCEPT_ENCODING)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| self.compression.accept(value))
+                .and_then(|value| HeaderValue::try_from(value).ok());
+
+            if let Some(accept) = accept {
+                request.headers_mut().insert(ACCEPT_ENCODING, accept);
+            }
+
+            self.service.call(request)
+        }
+    }
+
+    pub struct MinSize<S> {
+        service: Rc<S>,
+        min_size: u64,
+    }
+
+    impl<S, B> Service<ServiceRequest> for MinSize<S>
+    where
+        S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+        B: MessageBody + 'static,
+    {
+        type Response = ServiceResponse<B>;
+        type Error = Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+        forward_ready!(service);
+
+        fn call(&self, request: ServiceRequest) -> Self::Future {
+            let service = self.service.clone();
+            let min_size = self.min_size;
+
+            Box::pin(async move {
+                let mut response = service.call(request).await?;
+                let small = matches!(response.response().body().size(), BodySize::Sized(size) if size < min_size);
+
+                // Compress leaves responses that already declare an encoding alone
+                if small && !response.headers().contains_key(CONTENT_ENCODING) {
+                    response
+                        .headers_mut()
+                        .insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+                }
+
+                Ok(response)
+            })
+        }
+    }
+}
+
+#[cfg(all(test, feature = "compress-gzip"))]
+mod test {
+    use super::*;
+    use actix_web::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App};
+
+    async fn encoding(compression: Compression, body: usize, accept: &str) -> Option<String> {
+        let app = init_service(
+            App::new()
+                .wrap(compression)
+                .route("/", web::get().to(move || async move { "a".repeat(body) })),
+        )
+        .await;
+        let request = TestRequest::get()
+            .uri("/")
+            .insert_header((ACCEPT_ENCODING, accept))
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[actix_web::test]
+    async fn min_size() {
+        let compression = Compression::new().min_size(512);
+
+        assert_eq!(
+            encoding(compression.clone(), 1024, "gzip").await.as_deref(),
+            Some("gzip")
+        );
+        assert_ne!(
+            encoding(compression, 256, "gzip").await.as_deref(),
+            Some("gzip")
+        );
+    }
+
+    #[actix_web::test]
+    async fn algorithms() {
+        let compression = Compression::new().algorithms([ContentEncoding::Deflate]);
+
+        assert_ne!(
+            encoding(compression.clone(), 4096, "gzip").await.as_deref(),
+            Some("gzip")
+        );
+        assert_eq!(
+            encoding(compression.clone(), 4096, "gzip, deflate")
+                .await
+                .as_deref(),
+            Some("deflate")
+        );
+        assert_eq!(
+            encoding(compression, 4096, "*").await.as_deref(),
+            Some("deflate")
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderValue, RETRY_AFTER};
+use actix_web::rt::time::timeout;
+use actix_web::Error;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::responses;
+
+#[derive(Clone)]
+pub struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    timeout: Duration,
+    retry_after: u64,
+}
+
+impl ConcurrencyLimit {
+    pub fn new(permits: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(permits)),
+            timeout: Duration::from_millis(100),
+            retry_after: 1,
+        }
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn retry_after(mut self, seconds: u64) -> Self {
+        self.retry_after = seconds;
+        self
+    }
+
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    async fn acquire(semaphore: Arc<Semaphore>, wait: Duration) -> Option<OwnedSemaphorePermit> {
+        match wait.is_zero() {
+            true => semaphore.try_acquire_owned().ok(),
+            false => timeout(wait, semaphore.acquire_owned()).await.ok()?.ok(),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ConcurrencyLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ConcurrencyLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ConcurrencyLimitMiddleware {
+            service: Rc::new(service),
+            limit: self.clone(),
+        }))
+    }
+}
+
+pub struct ConcurrencyLimitMiddleware<S> {
+    service: Rc<S>,
+    limit: ConcurrencyLimit,
+}
+
+impl<S, B> Service<ServiceRequest> for ConcurrencyLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let semaphore = self.limit.semaphore.clone();
+        let wait = self.limit.timeout;
+        let retry_after = self.limit.retry_after;
+
+        Box::pin(async move {
+            let Some(permit) = ConcurrencyLimit::acquire(semaphore, wait).await else {
+                let error = responses::Error::ServiceUnavailable {
+                    message: "Too many concurrent requests".to_string(),
+                }
+                .with_cause(format!(
+                    "concurrency limit reached for {} {}",
+                    request.method(),
+                    request.path()
+                ));
+                let mut response = error.response();
+
+                response
+                    .headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+
+                return Ok(request.into_response(response).map_into_right_body());
+            };
+
+            let response = service.call(request).await;
+
+            drop(permit);
+
+            response.map(ServiceResponse::map_into_left_body)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::rt::time::sleep;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::HttpResponse;
+    use actix_web::{web, App};
+    use serde_json::Value;
+
+    async fn slow() -> HttpResponse {
+        sleep(Duration::from_millis(200)).await;
+
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_web::test]
+    async fn shed() {
+        let limit = ConcurrencyLimit::new(2).retry_after(5);
+        let app = init_service(
+            App::new()
+                .wrap(limit.clone())
+                .route("/", web::get().to(slow)),
+        )
+        .await;
+        let request = || call_service(&app, TestRequest::get().uri("/").to_request());
+        let (first, second, third) = futures_util::join!(request(), request(), async {
+            sleep(Duration::from_millis(50)).await;
+
+            request().await
+        });
+
+        assert_eq!(first.status(), 200);
+        assert_eq!(second.status(), 200);
+        assert_eq!(third.status(), 503);
+        assert_eq!(third.headers().get(RETRY_AFTER).unwrap(), "5");
+
+        let body: Value = read_body_json(third).await;
+
+        assert_eq!(body["message"], "Too many concurrent requests");
+        assert!(body["error_id"].is_string());
+        assert_eq!(limit.available(), 2);
+        assert_eq!(request().await.status(), 200);
+    }
+
+    #[actix_web::test]
+    async fn immediate() {
+        let limit = ConcurrencyLimit::new(1).timeout(Duration::ZERO);
+        let app = init_service(App::new().wrap(limit).route("/", web::get().to(slow))).await;
+        let request = || call_service(&app, TestRequest::get().uri("/").to_request());
+        let (first, second) = futures_util::join!(request(), request());
+
+        assert_eq!(first.status(), 200);
+        assert_eq!(second.status(), 503);
+    }
+
+    #[actix_web::test]
+    async fn wait() {
+        let limit = ConcurrencyLimit::new(1).timeout(Duration::from_millis(500));
+        let app = init_service(App::new().wrap(limit).route("/", web::get().to(slow))).await;
+        let request = || call_service(&app, TestRequest::get().uri("/").to_request());
+        let (first, second) = futures_util::join!(request(), request());
+
+        assert_eq!(first.status(), 200);
+        assert_eq!(second.status(), 200);
+    }
+}
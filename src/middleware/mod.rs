@@ -0,0 +1,9 @@
+mod catch_panic;
+mod compression;
+mod concurrency_limit;
+mod security_headers;
+
+pub use catch_panic::*;
+pub use compression::*;
+pub use concurrency_limit::*;
+pub use security_headers::*;
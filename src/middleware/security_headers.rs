@@ -0,0 +1,257 @@
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::InternalError;
+use actix_web::http::header::{
+    HeaderMap, HeaderName, HeaderValue, TryIntoHeaderValue, CONTENT_SECURITY_POLICY,
+    REFERRER_POLICY, STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+};
+use actix_web::Error;
+
+#[derive(Clone)]
+pub struct SecurityHeaders {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl SecurityHeaders {
+    pub fn new(tls: bool) -> Self {
+        let mut headers = Self { headers: vec![] }
+            .insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"))
+            .insert(X_FRAME_OPTIONS, HeaderValue::from_static("DENY"))
+            .insert(REFERRER_POLICY, HeaderValue::from_static("no-referrer"));
+
+        if tls {
+            headers = headers.insert(
+                STRICT_TRANSPORT_SECURITY,
+                HeaderValue::from_static("max-age=31536000; includeSubDomains"),
+            );
+        }
+
+        headers
+    }
+
+    pub fn header<V: TryIntoHeaderValue>(
+        self,
+        name: HeaderName,
+        value: V,
+    ) -> Result<Self, V::Error> {
+        Ok(self.insert(name, value.try_into_value()?))
+    }
+
+    pub fn remove(mut self, name: HeaderName) -> Self {
+        self.headers.retain(|(header, _)| header != name);
+        self
+    }
+
+    pub fn content_security_policy<V: TryIntoHeaderValue>(
+        self,
+        policy: V,
+    ) -> Result<Self, V::Error> {
+        self.header(CONTENT_SECURITY_POLICY, policy)
+    }
+
+    pub fn frame_options<V: TryIntoHeaderValue>(self, options: V) -> Result<Self, V::Error> {
+        self.header(X_FRAME_OPTIONS, options)
+    }
+
+    pub fn strict_transport_security<V: TryIntoHeaderValue>(
+        self,
+        value: V,
+    ) -> Result<Self, V::Error> {
+        self.header(STRICT_TRANSPORT_SECURITY, value)
+    }
+
+    fn insert(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self = self.remove(name.clone());
+        self.headers.push((name, value));
+        self
+    }
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for SecurityHeaders
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = SecurityHeadersMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(SecurityHeadersMiddleware {
+            service: Rc::new(service),
+            headers: Rc::new(self.headers.clone()),
+        }))
+    }
+}
+
+pub struct SecurityHeadersMiddleware<S> {
+    service: Rc<S>,
+    headers: Rc<Vec<(HeaderName, HeaderValue)>>,
+}
+
+impl<S> SecurityHeadersMiddleware<S> {
+    fn apply(headers: &[(HeaderName, HeaderValue)], map: &mut HeaderMap) {
+        for (name, value) in headers {
+            if !map.contains_key(name) {
+                map.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for SecurityHeadersMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let headers = self.headers.clone();
+
+        Box::pin(async move {
+            match service.call(request).await {
+                Ok(mut response) => {
+                    SecurityHeadersMiddleware::<S>::apply(&headers, response.headers_mut());
+                    Ok(response)
+                }
+                Err(error) => {
+                    let mut response = error.error_response();
+
+                    SecurityHeadersMiddleware::<S>::apply(&headers, response.headers_mut());
+                    Err(InternalError::from_response(error, response).into())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::test::{call_service, init_service, try_call_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    use crate::responses;
+
+    #[actix_web::test]
+    async fn plaintext() {
+        let app = init_service(
+            App::new()
+                .wrap(
+                    SecurityHeaders::new(false)
+                        .content_security_policy("default-src 'self'")
+                        .unwrap(),
+                )
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let response = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+        let headers = response.headers();
+
+        assert_eq!(headers.get(X_CONTENT_TYPE_OPTIONS).unwrap(), "nosniff");
+        assert_eq!(headers.get(X_FRAME_OPTIONS).unwrap(), "DENY");
+        assert_eq!(headers.get(REFERRER_POLICY).unwrap(), "no-referrer");
+        assert_eq!(
+            headers.get(CONTENT_SECURITY_POLICY).unwrap(),
+            "default-src 'self'"
+        );
+        assert!(headers.get(STRICT_TRANSPORT_SECURITY).is_none());
+    }
+
+    #[actix_web::test]
+    async fn tls() {
+        let app = init_service(
+            App::new()
+                .wrap(
+                    SecurityHeaders::new(true)
+                        .frame_options("SAMEORIGIN")
+                        .unwrap(),
+                )
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let response = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+        let headers = response.headers();
+
+        assert_eq!(headers.get(X_FRAME_OPTIONS).unwrap(), "SAMEORIGIN");
+        assert!(headers.get(STRICT_TRANSPORT_SECURITY).is_some());
+    }
+
+    #[actix_web::test]
+    async fn runtime() {
+        let nonce = "r4nd0m";
+        let headers = SecurityHeaders::new(true)
+            .content_security_policy(format!("script-src 'nonce-{}'", nonce))
+            .unwrap()
+            .strict_transport_security(format!("max-age={}", 86400))
+            .unwrap();
+        let app = init_service(
+            App::new()
+                .wrap(headers)
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let response = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+        let headers = response.headers();
+
+        assert_eq!(
+            headers.get(CONTENT_SECURITY_POLICY).unwrap(),
+            "script-src 'nonce-r4nd0m'"
+        );
+        assert_eq!(
+            headers.get(STRICT_TRANSPORT_SECURITY).unwrap(),
+            "max-age=86400"
+        );
+    }
+
+    #[actix_web::test]
+    async fn failure() {
+        let app = init_service(
+            App::new()
+                .wrap_fn(|_, _| async {
+                    Err::<ServiceResponse, _>(
+                        responses::Error::InternalServerError {
+                            message: "Internal server error".to_string(),
+                        }
+                        .into(),
+                    )
+                })
+                .wrap(SecurityHeaders::new(true))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let error = try_call_service(&app, TestRequest::get().uri("/").to_request())
+            .await
+            .unwrap_err();
+        let response = error.error_response();
+        let headers = response.headers();
+
+        assert_eq!(response.status(), 500);
+        assert_eq!(headers.get(X_CONTENT_TYPE_OPTIONS).unwrap(), "nosniff");
+        assert!(headers.get(STRICT_TRANSPORT_SECURITY).is_some());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(SecurityHeaders::default()
+            .content_security_policy("default-src\n'self'")
+            .is_err());
+    }
+}
@@ -1,20 +1,39 @@
 // re-exports
+#[cfg(feature = "web")]
 pub use actix_web::body::BoxBody;
+#[cfg(feature = "web")]
 pub use actix_web::http::StatusCode;
+#[cfg(feature = "web")]
 pub use actix_web::web::{
     self, Bytes, Data, Form, Header, Json, Path, Payload, Query as QueryParam, ServiceConfig,
 };
+#[cfg(feature = "web")]
 pub use actix_web::{HttpRequest, HttpResponse, Responder};
 pub use chrono::{self, NaiveDateTime};
-pub use lighter_common_derives::{PaginationRequest, PaginationResponse};
+#[cfg(feature = "derive")]
+pub use lighter_common_derives::{
+    CursorPaginationRequest, CursorPaginationResponse, PaginationRequest, PaginationResponse,
+};
+#[cfg(feature = "db")]
 pub use sea_orm::{
     self, Condition, DatabaseConnection, JoinType, Order, Set, TransactionError, TransactionTrait,
 };
 pub use uuid::{self, Uuid};
 
+#[cfg(feature = "web")]
+pub use crate::auth::BearerToken;
+#[cfg(feature = "db")]
+pub use crate::database;
 pub use crate::hash::Hash;
+#[cfg(feature = "web")]
+pub use crate::hash::ResourceId;
+#[cfg(feature = "web")]
+pub use crate::middleware::{CatchPanic, ConcurrencyLimit, SecurityHeaders};
+#[cfg(feature = "web")]
 pub use crate::responses::*;
+#[cfg(feature = "web")]
 pub use crate::server::Server;
 pub use crate::time::{now, unix};
+#[cfg(feature = "tracing")]
 pub use crate::tracing;
-pub use crate::{database, time};
+pub use crate::{cursor, time};
@@ -4,10 +4,11 @@ use std::fmt;
 use actix_web::body::BoxBody;
 use actix_web::http::StatusCode;
 use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
-use sea_orm::{DbErr, TransactionError};
-use serde_json::{json, Value};
-use utoipa::openapi::{ObjectBuilder, RefOr, Schema, SchemaType};
+use sea_orm::{DbErr, SqlErr, TransactionError};
+use serde_json::{json, Map, Value};
+use utoipa::openapi::{ArrayBuilder, ObjectBuilder, OneOfBuilder, RefOr, Schema, SchemaType};
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Clone, PartialEq, Eq)]
 pub enum Error {
@@ -27,6 +28,14 @@ pub enum Error {
     NotFound {
         message: String,
     },
+    // 410
+    Gone {
+        message: String,
+    },
+    // 413
+    PayloadTooLarge {
+        message: String,
+    },
     // 422
     UnprocessableEntity {
         errors: HashMap<String, Vec<String>>,
@@ -35,30 +44,155 @@ pub enum Error {
     InternalServerError {
         message: String,
     },
+    // 501
+    NotImplemented {
+        message: String,
+    },
+    // 502
+    BadGateway {
+        message: String,
+    },
+    // 503
+    ServiceUnavailable {
+        message: String,
+    },
+    // 504
+    GatewayTimeout {
+        message: String,
+    },
+    // built by with_cause and with_detail, the cause is logged with the error_id
+    #[doc(hidden)]
+    Context(Context),
+}
+
+#[doc(hidden)]
+#[derive(Clone, PartialEq, Eq)]
+pub struct Context {
+    error: Box<Error>,
+    cause: Option<String>,
+    details: Map<String, Value>,
 }
 
 impl Error {
+    pub fn not_found_resource<R: fmt::Display, I: fmt::Display>(resource: R, id: I) -> Self {
+        Self::NotFound {
+            message: format!("{resource} with id {id} not found"),
+        }
+        .with_detail("resource", resource.to_string())
+        .with_detail("id", id.to_string())
+    }
+
+    fn context(self) -> Context {
+        match self {
+            Self::Context(context) => context,
+            error => Context {
+                error: Box::new(error),
+                cause: None,
+                details: Map::new(),
+            },
+        }
+    }
+
+    pub fn with_cause<C: fmt::Display>(self, cause: C) -> Self {
+        let mut context = self.context();
+
+        context.cause = Some(cause.to_string());
+
+        Self::Context(context)
+    }
+
+    pub fn with_detail<K: ToString, V: Into<Value>>(self, key: K, value: V) -> Self {
+        let mut context = self.context();
+
+        context.details.insert(key.to_string(), value.into());
+
+        Self::Context(context)
+    }
+
+    pub fn from_db(value: DbErr, fields: &[(&str, &str)]) -> Self {
+        use DbErr::*;
+
+        if let Some(SqlErr::UniqueConstraintViolation(message)) = value.sql_err() {
+            tracing::warn!(cause = message, "unique constraint violation");
+
+            let field = match constraint(&message) {
+                Some(name) => match fields.iter().find(|(constraint, _)| *constraint == name) {
+                    Some((_, field)) => field.to_string(),
+                    None => name.to_string(),
+                },
+                None => "constraint".to_string(),
+            };
+
+            return Self::UnprocessableEntity {
+                errors: HashMap::from([(field, vec!["Value already exists".to_string()])]),
+            };
+        }
+
+        match value {
+            RecordNotFound(_) => Self::NotFound {
+                message: "Not found".to_string(),
+            },
+            _ => Self::InternalServerError {
+                message: "Internal server error".to_string(),
+            }
+            .with_cause(value),
+        }
+    }
+
+    pub fn cause(&self) -> Option<&str> {
+        match self {
+            Self::Context(context) => context.cause.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn details(&self) -> Option<&Map<String, Value>> {
+        match self {
+            Self::Context(context) => Some(&context.details),
+            _ => None,
+        }
+    }
+
     pub fn json(&self) -> Value {
+        if let Self::Context(context) = self {
+            let mut json = context.error.json();
+
+            if let Some(body) = json.as_object_mut() {
+                body.extend(context.details.clone());
+            }
+
+            return json;
+        }
+
         if let Self::UnprocessableEntity { errors } = self {
             return json!({
                 "errors": errors
             });
         }
 
-        let message = match self {
-            Self::BadRequest { message } => message,
-            Self::Unauthorized { message } => message,
-            Self::Forbidden { message } => message,
-            Self::NotFound { message } => message,
-            Self::InternalServerError { message } => message,
-            _ => "Unknown error",
-        };
-
         json!({
-            "message": message,
+            "message": self.message().unwrap_or("Unknown error"),
         })
     }
 
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Self::BadRequest { message } => Some(message),
+            Self::Unauthorized { message } => Some(message),
+            Self::Forbidden { message } => Some(message),
+            Self::NotFound { message } => Some(message),
+            Self::Gone { message } => Some(message),
+            Self::PayloadTooLarge { message } => Some(message),
+            Self::InternalServerError { message } => Some(message),
+            Self::NotImplemented { message } => Some(message),
+            Self::BadGateway { message } => Some(message),
+            Self::ServiceUnavailable { message } => Some(message),
+            Self::GatewayTimeout { message } => Some(message),
+            Self::UnprocessableEntity { errors: _ } => None,
+            Self::Context(context) => context.error.message(),
+        }
+    }
+
     pub fn status_code(&self) -> StatusCode {
         use Error::*;
 
@@ -67,40 +201,64 @@ impl Error {
             Unauthorized { message: _ } => StatusCode::UNAUTHORIZED,
             Forbidden { message: _ } => StatusCode::FORBIDDEN,
             NotFound { message: _ } => StatusCode::NOT_FOUND,
+            Gone { message: _ } => StatusCode::GONE,
+            PayloadTooLarge { message: _ } => StatusCode::PAYLOAD_TOO_LARGE,
             UnprocessableEntity { errors: _ } => StatusCode::UNPROCESSABLE_ENTITY,
             InternalServerError { message: _ } => StatusCode::INTERNAL_SERVER_ERROR,
+            NotImplemented { message: _ } => StatusCode::NOT_IMPLEMENTED,
+            BadGateway { message: _ } => StatusCode::BAD_GATEWAY,
+            ServiceUnavailable { message: _ } => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayTimeout { message: _ } => StatusCode::GATEWAY_TIMEOUT,
+            Context(context) => context.error.status_code(),
         }
     }
 
     pub fn response(&self) -> HttpResponse {
-        use Error::*;
+        let mut body = self.json();
 
-        let mut response = match self {
-            BadRequest { message: _ } => HttpResponse::BadRequest(),
-            Unauthorized { message: _ } => HttpResponse::Unauthorized(),
-            Forbidden { message: _ } => HttpResponse::Forbidden(),
-            NotFound { message: _ } => HttpResponse::NotFound(),
-            UnprocessableEntity { errors: _ } => HttpResponse::UnprocessableEntity(),
-            InternalServerError { message: _ } => HttpResponse::InternalServerError(),
-        };
+        if self.status_code().is_server_error() {
+            let error_id = Uuid::new_v4();
+
+            match self.cause() {
+                Some(cause) => tracing::error!(%error_id, cause, "{}", self),
+                None => tracing::error!(%error_id, "{}", self),
+            }
 
-        response.json(self.json())
+            body["error_id"] = json!(error_id);
+        }
+
+        HttpResponse::build(self.status_code()).json(body)
     }
 }
 
 impl From<DbErr> for Error {
     fn from(value: DbErr) -> Self {
-        use DbErr::*;
+        Self::from_db(value, &[])
+    }
+}
 
-        match value {
-            RecordNotFound(_) => Self::NotFound {
-                message: "Not found".to_string(),
-            },
-            _ => Self::InternalServerError {
-                message: value.to_string(),
-            },
-        }
+fn constraint(message: &str) -> Option<&str> {
+    // sqlite: UNIQUE constraint failed: users.email
+    if let Some((_, columns)) = message.split_once("constraint failed: ") {
+        let columns = columns.trim();
+
+        return match columns.contains(',') {
+            true => Some(columns),
+            false => columns.rsplit('.').next(),
+        };
     }
+
+    // postgres: ... violates unique constraint "users_email_key"
+    // mysql: Duplicate entry '...' for key 'users.users_email_key'
+    let quote = if message.contains("unique constraint") {
+        '"'
+    } else {
+        '\''
+    };
+    let (rest, _) = message.trim_end().rsplit_once(quote)?;
+    let (_, name) = rest.rsplit_once(quote)?;
+
+    Some(name.rsplit('.').next().unwrap_or(name))
 }
 
 impl From<serde_json::Error> for Error {
@@ -111,11 +269,53 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::InternalServerError {
+            message: "Internal server error".to_string(),
+        }
+        .with_cause(value)
+    }
+}
+
 impl From<TransactionError<DbErr>> for Error {
     fn from(value: TransactionError<DbErr>) -> Self {
+        match value {
+            TransactionError::Connection(error) | TransactionError::Transaction(error) => {
+                Self::from(error)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for Error {
+    fn from(value: anyhow::Error) -> Self {
         Self::InternalServerError {
-            message: value.to_string(),
+            message: "Internal server error".to_string(),
         }
+        .with_cause(format!("{:#}", value))
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        let error = if value.is_timeout() {
+            Self::GatewayTimeout {
+                message: "Upstream request timed out".to_string(),
+            }
+        } else if value.is_decode() {
+            Self::InternalServerError {
+                message: "Failed to decode upstream response".to_string(),
+            }
+        } else {
+            Self::BadGateway {
+                message: "Upstream request failed".to_string(),
+            }
+        };
+
+        error.with_cause(value)
     }
 }
 
@@ -147,7 +347,7 @@ impl fmt::Debug for Error {
 
 impl ToSchema<'_> for Error {
     fn schema() -> (&'static str, RefOr<Schema>) {
-        let schema = ObjectBuilder::new()
+        let message = ObjectBuilder::new()
             .schema_type(SchemaType::Object)
             .property(
                 "message",
@@ -156,8 +356,349 @@ impl ToSchema<'_> for Error {
                     .example(Some(json!("Not found")))
                     .build(),
             )
+            .required("message")
+            .build();
+        let errors = ObjectBuilder::new()
+            .schema_type(SchemaType::Object)
+            .property(
+                "errors",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Object)
+                    .additional_properties(Some(
+                        ArrayBuilder::new()
+                            .items(ObjectBuilder::new().schema_type(SchemaType::String)),
+                    ))
+                    .example(Some(json!({ "email": ["Email is required"] })))
+                    .build(),
+            )
+            .required("errors")
             .build();
+        let schema = OneOfBuilder::new().item(message).item(errors).build();
+
+        ("Error", Schema::OneOf(schema).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::responses::{BadGateway, GatewayTimeout, Gone, NotImplemented, ServiceUnavailable};
+    use actix_web::body::to_bytes;
+
+    #[test]
+    fn schema() {
+        let (name, schema) = Error::schema();
+        let schema = serde_json::to_value(schema).unwrap();
+        let shapes = schema["oneOf"].as_array().unwrap();
+
+        assert_eq!(name, "Error");
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shapes[0]["properties"]["message"]["type"], "string");
+        assert_eq!(shapes[1]["properties"]["errors"]["type"], "object");
+        assert_eq!(
+            shapes[1]["properties"]["errors"]["additionalProperties"]["items"]["type"],
+            "string"
+        );
+    }
+
+    #[actix_web::test]
+    async fn error_id() {
+        let error = Error::InternalServerError {
+            message: "Connection refused".to_string(),
+        };
+        let body = to_bytes(error.response().into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert!(body["error_id"]
+            .as_str()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .is_some());
+    }
+
+    #[actix_web::test]
+    async fn caused() {
+        let error = Error::InternalServerError {
+            message: "Internal server error".to_string(),
+        }
+        .with_cause("connection reset by peer");
+
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.cause(), Some("connection reset by peer"));
+        assert_eq!(error.message(), Some("Internal server error"));
+
+        let body = to_bytes(error.response().into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body["message"], "Internal server error");
+        assert!(body["error_id"].is_string());
+        assert!(!body.to_string().contains("connection reset"));
+
+        let error = error.with_cause("broken pipe");
+
+        assert_eq!(error.cause(), Some("broken pipe"));
+        assert_eq!(error.message(), Some("Internal server error"));
+    }
+
+    #[actix_web::test]
+    async fn no_error_id() {
+        let error = Error::NotFound {
+            message: "Not found".to_string(),
+        };
+        let body = to_bytes(error.response().into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert!(body.get("error_id").is_none());
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow() {
+        let error: Error = anyhow::anyhow!("password=secret").into();
+
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.json(), json!({ "message": "Internal server error" }));
+        assert_eq!(error.cause(), Some("password=secret"));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[actix_web::test]
+    async fn unique_violation() {
+        use sea_orm::ConnectionTrait;
+
+        let db = crate::database::memory().await.unwrap();
+
+        db.execute_unprepared("CREATE TABLE users (email TEXT UNIQUE)")
+            .await
+            .unwrap();
+        db.execute_unprepared("INSERT INTO users VALUES ('john@example.com')")
+            .await
+            .unwrap();
+
+        let error: Error = db
+            .execute_unprepared("INSERT INTO users VALUES ('john@example.com')")
+            .await
+            .unwrap_err()
+            .into();
+
+        assert_eq!(error.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            error.json()["errors"]["email"],
+            json!(["Value already exists"])
+        );
+    }
+
+    #[test]
+    fn constraint() {
+        use super::constraint;
+
+        let messages = [
+            ("UNIQUE constraint failed: users.email", Some("email")),
+            (
+                "UNIQUE constraint failed: users.first, users.last",
+                Some("users.first, users.last"),
+            ),
+            (
+                "duplicate key value violates unique constraint \"users_email_key\"",
+                Some("users_email_key"),
+            ),
+            (
+                "Duplicate entry 'john@example.com' for key 'users.users_email_key'",
+                Some("users_email_key"),
+            ),
+            ("unexpected", None),
+        ];
+
+        for (message, expected) in messages {
+            assert_eq!(constraint(message), expected, "{message}");
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[actix_web::test]
+    async fn unique_violation_transaction() {
+        use sea_orm::{ConnectionTrait, TransactionTrait};
+
+        let db = crate::database::memory().await.unwrap();
+
+        db.execute_unprepared("CREATE TABLE users (email TEXT UNIQUE)")
+            .await
+            .unwrap();
+
+        let error: Error = db
+            .transaction::<_, (), DbErr>(|txn| {
+                Box::pin(async move {
+                    txn.execute_unprepared("INSERT INTO users VALUES ('john@example.com')")
+                        .await?;
+                    txn.execute_unprepared("INSERT INTO users VALUES ('john@example.com')")
+                        .await?;
+
+                    Ok(())
+                })
+            })
+            .await
+            .unwrap_err()
+            .into();
+
+        assert_eq!(error.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            error.json(),
+            json!({ "errors": { "email": ["Value already exists"] } })
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[actix_web::test]
+    async fn unique_violation_mapping() {
+        use sea_orm::ConnectionTrait;
+
+        let db = crate::database::memory().await.unwrap();
+
+        db.execute_unprepared("CREATE TABLE users (email TEXT UNIQUE)")
+            .await
+            .unwrap();
+        db.execute_unprepared("INSERT INTO users VALUES ('john@example.com')")
+            .await
+            .unwrap();
+
+        let error = db
+            .execute_unprepared("INSERT INTO users VALUES ('john@example.com')")
+            .await
+            .unwrap_err();
+        let error = Error::from_db(error, &[("email", "email_address")]);
+
+        assert_eq!(
+            error.json(),
+            json!({ "errors": { "email_address": ["Value already exists"] } })
+        );
+    }
+
+    #[test]
+    fn record_not_found() {
+        let error: Error = DbErr::RecordNotFound("users".to_string()).into();
+
+        assert_eq!(error.status_code(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn db() {
+        let error: Error = DbErr::Custom("relation \"users\" does not exist".to_string()).into();
+
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.json(), json!({ "message": "Internal server error" }));
+        assert!(error.cause().unwrap().contains("relation \"users\""));
+    }
+
+    #[test]
+    fn not_found_resource() {
+        let error = Error::not_found_resource("user", 42);
+
+        assert_eq!(error.status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(error.message(), Some("user with id 42 not found"));
+        assert_eq!(
+            error.json(),
+            json!({
+                "message": "user with id 42 not found",
+                "resource": "user",
+                "id": "42",
+            })
+        );
+
+        let error = error.with_cause("SELECT returned no rows");
+
+        assert_eq!(error.cause(), Some("SELECT returned no rows"));
+        assert_eq!(error.details().unwrap()["id"], "42");
+        assert_eq!(error.json()["resource"], "user");
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[actix_web::test]
+    async fn reqwest() {
+        use std::net::TcpListener;
+        use std::time::Duration;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let error: Error = client
+            .get(format!("http://127.0.0.1:{port}"))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+
+        assert_eq!(error.status_code(), StatusCode::GATEWAY_TIMEOUT);
+
+        drop(listener);
+
+        let error: Error = client
+            .get(format!("http://127.0.0.1:{port}"))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+
+        assert_eq!(error.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(
+            error.json(),
+            json!({ "message": "Upstream request failed" })
+        );
+    }
+
+    #[test]
+    fn io() {
+        let error: Error = std::fs::read("/nonexistent/lighter-common")
+            .unwrap_err()
+            .into();
+
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        assert_eq!(error.json(), json!({ "message": "Internal server error" }));
+        assert!(error.cause().unwrap().contains("No such file"));
+    }
+
+    #[test]
+    fn gone() {
+        let error: Error = Gone::new("User was deleted").into();
+
+        assert_eq!(error.status_code(), StatusCode::GONE);
+        assert_eq!(error.json(), json!({ "message": "User was deleted" }));
+    }
+
+    #[test]
+    fn not_implemented() {
+        let error: Error = NotImplemented::new("Not implemented").into();
+
+        assert_eq!(error.status_code(), StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(error.json(), json!({ "message": "Not implemented" }));
+    }
+
+    #[test]
+    fn bad_gateway() {
+        let error: Error = BadGateway::new("Upstream unavailable").into();
+
+        assert_eq!(error.status_code(), StatusCode::BAD_GATEWAY);
+        assert_eq!(error.json(), json!({ "message": "Upstream unavailable" }));
+    }
+
+    #[test]
+    fn service_unavailable() {
+        let error: Error = ServiceUnavailable::new("Too many requests in flight").into();
+
+        assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            error.json(),
+            json!({ "message": "Too many requests in flight" })
+        );
+    }
+
+    #[test]
+    fn gateway_timeout() {
+        let error: Error = GatewayTimeout::new("Upstream timed out").into();
 
-        ("Error", schema.into())
+        assert_eq!(error.status_code(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(error.json(), json!({ "message": "Upstream timed out" }));
     }
 }
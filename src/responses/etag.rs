@@ -0,0 +1,87 @@
+use actix_web::body::BoxBody;
+use actix_web::http::header::{ETag, EntityTag, Header, IfNoneMatch};
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use serde::Serialize;
+
+use crate::hash::Hash;
+
+pub struct WithEtag<T: Serialize>(pub T);
+
+impl<T: Serialize> WithEtag<T> {
+    pub fn new(body: T) -> Self {
+        Self(body)
+    }
+}
+
+impl<T: Serialize> Responder for WithEtag<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, request: &HttpRequest) -> HttpResponse<Self::Body> {
+        let body = match serde_json::to_vec(&self.0) {
+            Ok(body) => body,
+            Err(e) => return crate::responses::Error::from(e).response(),
+        };
+        let tag = EntityTag::new_strong(Hash::make("", &body).to_string());
+        let matched = match IfNoneMatch::parse(request) {
+            Ok(IfNoneMatch::Any) => true,
+            Ok(IfNoneMatch::Items(items)) => items.iter().any(|item| item.weak_eq(&tag)),
+            Err(_) => false,
+        };
+
+        if matched {
+            return HttpResponse::NotModified()
+                .insert_header(ETag(tag))
+                .finish();
+        }
+
+        HttpResponse::Ok()
+            .insert_header(ETag(tag))
+            .content_type("application/json")
+            .body(body)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::http::header::{ETAG, IF_NONE_MATCH};
+    use actix_web::test::TestRequest;
+    use serde_json::{json, Value};
+
+    #[actix_web::test]
+    async fn fresh() {
+        let request = TestRequest::default().to_http_request();
+        let response = WithEtag::new(json!({ "id": 1 })).respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+        assert!(response.headers().get(ETAG).is_some());
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body, json!({ "id": 1 }));
+    }
+
+    #[actix_web::test]
+    async fn not_modified() {
+        let request = TestRequest::default().to_http_request();
+        let response = WithEtag::new(json!({ "id": 1 })).respond_to(&request);
+        let etag = response.headers().get(ETAG).unwrap().clone();
+
+        let request = TestRequest::default()
+            .insert_header((IF_NONE_MATCH, etag.clone()))
+            .to_http_request();
+        let response = WithEtag::new(json!({ "id": 1 })).respond_to(&request);
+
+        assert_eq!(response.status(), 304);
+        assert_eq!(response.headers().get(ETAG).unwrap(), etag);
+
+        let request = TestRequest::default()
+            .insert_header((IF_NONE_MATCH, etag))
+            .to_http_request();
+        let response = WithEtag::new(json!({ "id": 2 })).respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+    }
+}
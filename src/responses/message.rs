@@ -1,15 +1,410 @@
+use std::collections::BTreeMap;
+
 use actix_web::body::BoxBody;
+use actix_web::http::header::LOCATION;
 use actix_web::{HttpRequest, HttpResponse, Responder};
-use utoipa::IntoResponses;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::openapi::response::{ResponseBuilder, ResponsesBuilder};
+use utoipa::openapi::{Content, ObjectBuilder, Ref, RefOr, Response, SchemaType};
+use utoipa::{IntoResponses, ToSchema};
 
-#[derive(IntoResponses)]
-#[response(status = 200, description = "Ok")]
-pub struct Success;
+#[derive(Default)]
+pub struct Success {
+    message: Option<String>,
+}
+
+impl Success {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    pub fn with_message<M: ToString>(message: M) -> Self {
+        Self {
+            message: Some(message.to_string()),
+        }
+    }
+}
 
 impl Responder for Success {
     type Body = BoxBody;
 
     fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
-        HttpResponse::Ok().finish()
+        match self.message {
+            Some(message) => HttpResponse::Ok().json(json!({ "message": message })),
+            None => HttpResponse::Ok().finish(),
+        }
+    }
+}
+
+impl IntoResponses for Success {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        ResponsesBuilder::new()
+            .response("200", ResponseBuilder::new().description("Ok"))
+            .build()
+            .into()
+    }
+}
+
+#[derive(IntoResponses)]
+#[response(status = 204, description = "No Content")]
+pub struct NoContent;
+
+impl Responder for NoContent {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::NoContent().finish()
+    }
+}
+
+pub struct Created<T: Serialize> {
+    body: T,
+    location: Option<String>,
+}
+
+impl<T: Serialize> Created<T> {
+    pub fn new(body: T) -> Self {
+        Self {
+            body,
+            location: None,
+        }
+    }
+
+    pub fn location<L: ToString>(mut self, location: L) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+}
+
+impl<T: Serialize> Responder for Created<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        let mut response = HttpResponse::Created();
+
+        if let Some(location) = self.location {
+            response.insert_header((LOCATION, location));
+        }
+
+        response.json(self.body)
+    }
+}
+
+impl<'s, T: Serialize + ToSchema<'s>> IntoResponses for Created<T> {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        ResponsesBuilder::new()
+            .response("201", body::<T>("Created"))
+            .build()
+            .into()
+    }
+}
+
+#[derive(Serialize)]
+pub struct NoBody;
+
+pub struct Accepted<T: Serialize = NoBody> {
+    body: Option<T>,
+}
+
+impl Accepted {
+    pub fn empty() -> Self {
+        Self { body: None }
+    }
+}
+
+impl<T: Serialize> Accepted<T> {
+    pub fn new(body: T) -> Self {
+        Self { body: Some(body) }
+    }
+}
+
+impl<T: Serialize> Responder for Accepted<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        match self.body {
+            Some(body) => HttpResponse::Accepted().json(body),
+            None => HttpResponse::Accepted().finish(),
+        }
+    }
+}
+
+impl<'s, T: Serialize + ToSchema<'s>> IntoResponses for Accepted<T> {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        ResponsesBuilder::new()
+            .response("202", body::<T>("Accepted"))
+            .build()
+            .into()
+    }
+}
+
+impl IntoResponses for Accepted<NoBody> {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        ResponsesBuilder::new()
+            .response("202", ResponseBuilder::new().description("Accepted"))
+            .build()
+            .into()
+    }
+}
+
+fn body<'s, T: ToSchema<'s>>(description: &str) -> ResponseBuilder {
+    let (name, _) = T::schema();
+
+    ResponseBuilder::new().description(description).content(
+        "application/json",
+        Content::new(Ref::from_schema_name(name)),
+    )
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DataResponse<T> {
+    pub data: T,
+}
+
+impl<T: Serialize> DataResponse<T> {
+    pub fn new(data: T) -> Self {
+        Self { data }
+    }
+}
+
+impl<T: Serialize> Responder for DataResponse<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
+
+impl<'s, T: Serialize + ToSchema<'s>> IntoResponses for DataResponse<T> {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        let (name, _) = T::schema();
+        let schema = ObjectBuilder::new()
+            .schema_type(SchemaType::Object)
+            .property("data", Ref::from_schema_name(name))
+            .required("data")
+            .build();
+
+        ResponsesBuilder::new()
+            .response(
+                "200",
+                ResponseBuilder::new()
+                    .description("Ok")
+                    .content("application/json", Content::new(schema)),
+            )
+            .build()
+            .into()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Envelope<T> {
+    pub data: T,
+    pub meta: BTreeMap<String, Value>,
+}
+
+impl<T: Serialize> Envelope<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            meta: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_meta<K: ToString, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.meta.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+impl<T: Serialize> Responder for Envelope<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
+
+impl<'s, T: Serialize + ToSchema<'s>> IntoResponses for Envelope<T> {
+    fn responses() -> BTreeMap<String, RefOr<Response>> {
+        let (name, _) = T::schema();
+        let schema = ObjectBuilder::new()
+            .schema_type(SchemaType::Object)
+            .property("data", Ref::from_schema_name(name))
+            .property("meta", ObjectBuilder::new().schema_type(SchemaType::Object))
+            .required("data")
+            .required("meta")
+            .build();
+
+        ResponsesBuilder::new()
+            .response(
+                "200",
+                ResponseBuilder::new()
+                    .description("Ok")
+                    .content("application/json", Content::new(schema)),
+            )
+            .build()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::test::TestRequest;
+
+    #[actix_web::test]
+    async fn success() {
+        let request = TestRequest::default().to_http_request();
+        let response = Success::empty().respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+
+        assert!(body.is_empty());
+
+        let response = Success::with_message("ok").respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body, json!({ "message": "ok" }));
+        assert!(Success::responses().contains_key("200"));
+    }
+
+    #[actix_web::test]
+    async fn created() {
+        let request = TestRequest::default().to_http_request();
+        let response = Created::new(json!({ "id": 1, "name": "John" }))
+            .location("/users/1")
+            .respond_to(&request);
+
+        assert_eq!(response.status(), 201);
+        assert_eq!(response.headers().get(LOCATION).unwrap(), "/users/1");
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body, json!({ "id": 1, "name": "John" }));
+    }
+
+    #[test]
+    fn created_responses() {
+        let responses = Created::<crate::responses::BadRequest>::responses();
+        let response = serde_json::to_value(&responses["201"]).unwrap();
+
+        assert_eq!(
+            response["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/BadRequest"
+        );
+    }
+
+    #[actix_web::test]
+    async fn no_content() {
+        let request = TestRequest::default().to_http_request();
+        let response = NoContent.respond_to(&request);
+
+        assert_eq!(response.status(), 204);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+
+        assert!(body.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn accepted() {
+        let request = TestRequest::default().to_http_request();
+        let response = Accepted::new(json!({ "job": "42" })).respond_to(&request);
+
+        assert_eq!(response.status(), 202);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body, json!({ "job": "42" }));
+
+        let response = Accepted::empty().respond_to(&request);
+
+        assert_eq!(response.status(), 202);
+    }
+
+    #[test]
+    fn accepted_responses() {
+        let responses = Accepted::<crate::responses::BadRequest>::responses();
+        let response = serde_json::to_value(&responses["202"]).unwrap();
+
+        assert_eq!(
+            response["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/BadRequest"
+        );
+
+        let responses = Accepted::<NoBody>::responses();
+        let response = serde_json::to_value(&responses["202"]).unwrap();
+
+        assert!(response.get("content").is_none());
+    }
+
+    #[actix_web::test]
+    async fn data() {
+        let request = TestRequest::default().to_http_request();
+        let response = DataResponse::new(vec!["John", "Jane"]).respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let data: DataResponse<Vec<String>> = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(data.data, vec!["John", "Jane"]);
+    }
+
+    #[test]
+    fn data_responses() {
+        let responses = DataResponse::<crate::responses::BadRequest>::responses();
+        let response = serde_json::to_value(&responses["200"]).unwrap();
+
+        assert_eq!(
+            response["content"]["application/json"]["schema"]["properties"]["data"]["$ref"],
+            "#/components/schemas/BadRequest"
+        );
+    }
+
+    #[actix_web::test]
+    async fn envelope() {
+        let request = TestRequest::default().to_http_request();
+        let response = Envelope::new(vec!["John", "Jane"])
+            .with_meta("took_ms", 12)
+            .with_meta("version", "1.0")
+            .with_meta("tags", json!(["a", "b"]))
+            .respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "data": ["John", "Jane"],
+                "meta": { "took_ms": 12, "version": "1.0", "tags": ["a", "b"] },
+            })
+        );
+    }
+
+    #[test]
+    fn envelope_responses() {
+        let responses = Envelope::<crate::responses::BadRequest>::responses();
+        let response = serde_json::to_value(&responses["200"]).unwrap();
+        let schema = &response["content"]["application/json"]["schema"];
+
+        assert_eq!(
+            schema["properties"]["data"]["$ref"],
+            "#/components/schemas/BadRequest"
+        );
+        assert_eq!(schema["properties"]["meta"]["type"], "object");
     }
 }
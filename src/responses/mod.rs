@@ -1,10 +1,21 @@
 mod error;
+mod etag;
 mod message;
+mod ndjson;
+#[cfg(feature = "rmp-serde")]
+mod negotiated;
 mod pagination;
+mod problem;
 mod schema;
 mod validation;
 
 pub use error::*;
+pub use etag::*;
 pub use message::*;
+pub use ndjson::*;
+#[cfg(feature = "rmp-serde")]
+pub use negotiated::*;
+pub use pagination::*;
+pub use problem::*;
 pub use schema::*;
 pub use validation::*;
@@ -0,0 +1,81 @@
+use actix_web::body::BoxBody;
+use actix_web::web::Bytes;
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+
+use super::Error;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+pub struct NdJson<S>(pub S);
+
+impl<S> NdJson<S> {
+    pub fn new(stream: S) -> Self {
+        Self(stream)
+    }
+}
+
+impl<S, T, E> Responder for NdJson<S>
+where
+    S: Stream<Item = Result<T, E>> + 'static,
+    T: Serialize,
+    E: Into<Error>,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        let lines = self.0.map(|item| {
+            let mut line = serde_json::to_vec(&item.map_err(Into::into)?).map_err(Error::from)?;
+
+            line.push(b'\n');
+
+            Ok::<_, actix_web::Error>(Bytes::from(line))
+        });
+
+        HttpResponse::Ok()
+            .content_type(NDJSON_CONTENT_TYPE)
+            .streaming(lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::test::TestRequest;
+    use futures_util::stream;
+    use serde_json::json;
+
+    #[actix_web::test]
+    async fn framing() {
+        let request = TestRequest::default().to_http_request();
+        let users = (1..=3).map(|id| Ok::<_, Error>(json!({ "id": id })));
+        let response = NdJson::new(stream::iter(users)).respond_to(&request);
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            NDJSON_CONTENT_TYPE
+        );
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+
+        assert_eq!(body, "{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n");
+    }
+
+    #[actix_web::test]
+    async fn failure() {
+        let request = TestRequest::default().to_http_request();
+        let items = vec![
+            Ok(json!({ "id": 1 })),
+            Err(Error::InternalServerError {
+                message: "Connection lost".to_string(),
+            }),
+        ];
+        let response = NdJson::new(stream::iter(items)).respond_to(&request);
+
+        assert!(to_bytes(response.into_body()).await.is_err());
+    }
+}
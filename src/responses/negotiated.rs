@@ -0,0 +1,176 @@
+use actix_web::body::BoxBody;
+use actix_web::http::header::{ACCEPT, VARY};
+use actix_web::{HttpRequest, HttpResponse, Responder};
+use serde::Serialize;
+
+use super::error::Error;
+
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+pub struct Negotiated<T: Serialize>(pub T);
+
+impl<T: Serialize> Negotiated<T> {
+    pub fn new(body: T) -> Self {
+        Self(body)
+    }
+
+    fn quality(accept: &str, types: &[&str]) -> f32 {
+        let mut best = (0, 0.0);
+
+        for range in accept.split(',') {
+            let mut parts = range.split(';');
+            let media = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let specificity = if types.iter().any(|t| media.eq_ignore_ascii_case(t)) {
+                3
+            } else if media.eq_ignore_ascii_case("application/*") {
+                2
+            } else if media == "*/*" {
+                1
+            } else {
+                0
+            };
+
+            if specificity > best.0 {
+                best = (specificity, quality);
+            }
+        }
+
+        best.1
+    }
+}
+
+impl<T: Serialize> Responder for Negotiated<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, request: &HttpRequest) -> HttpResponse<Self::Body> {
+        let accept = request
+            .headers()
+            .get(ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .unwrap_or("*/*");
+        let json = Self::quality(accept, &["application/json"]);
+        let msgpack = Self::quality(accept, &[MSGPACK_CONTENT_TYPE, "application/x-msgpack"]);
+
+        if json <= 0.0 && msgpack <= 0.0 {
+            return HttpResponse::NotAcceptable()
+                .insert_header((VARY, "Accept"))
+                .finish();
+        }
+
+        if json >= msgpack {
+            return HttpResponse::Ok()
+                .insert_header((VARY, "Accept"))
+                .json(self.0);
+        }
+
+        match rmp_serde::to_vec_named(&self.0) {
+            Ok(body) => HttpResponse::Ok()
+                .insert_header((VARY, "Accept"))
+                .content_type(MSGPACK_CONTENT_TYPE)
+                .body(body),
+            Err(e) => Error::InternalServerError {
+                message: "Internal server error".to_string(),
+            }
+            .with_cause(e)
+            .response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::test::TestRequest;
+    use serde_json::{json, Value};
+
+    #[actix_web::test]
+    async fn json() {
+        for accept in [None, Some("application/json"), Some("*/*")] {
+            let mut request = TestRequest::default();
+
+            if let Some(accept) = accept {
+                request = request.insert_header((ACCEPT, accept));
+            }
+
+            let response =
+                Negotiated::new(json!({ "id": 1 })).respond_to(&request.to_http_request());
+
+            assert_eq!(
+                response.headers().get(CONTENT_TYPE).unwrap(),
+                "application/json"
+            );
+
+            let body = to_bytes(response.into_body()).await.unwrap();
+            let body: Value = serde_json::from_slice(&body).unwrap();
+
+            assert_eq!(body, json!({ "id": 1 }));
+        }
+    }
+
+    #[actix_web::test]
+    async fn msgpack() {
+        for accept in [
+            "application/msgpack",
+            "application/json;q=0.5, application/x-msgpack",
+        ] {
+            let request = TestRequest::default()
+                .insert_header((ACCEPT, accept))
+                .to_http_request();
+            let response = Negotiated::new(json!({ "id": 1 })).respond_to(&request);
+
+            assert_eq!(
+                response.headers().get(CONTENT_TYPE).unwrap(),
+                MSGPACK_CONTENT_TYPE
+            );
+
+            let body = to_bytes(response.into_body()).await.unwrap();
+            let body: Value = rmp_serde::from_slice(&body).unwrap();
+
+            assert_eq!(body, json!({ "id": 1 }));
+        }
+    }
+
+    #[actix_web::test]
+    async fn quality() {
+        for (accept, expected) in [
+            (
+                "application/msgpack;q=0.4, application/json;q=0.8",
+                "application/json",
+            ),
+            (
+                "application/json;q=0.2, */*;q=0.1, application/msgpack",
+                MSGPACK_CONTENT_TYPE,
+            ),
+            (
+                "application/*;q=0.5, application/msgpack;q=0.9",
+                MSGPACK_CONTENT_TYPE,
+            ),
+            (
+                "application/json;q=0, application/msgpack;q=0.1",
+                MSGPACK_CONTENT_TYPE,
+            ),
+        ] {
+            let request = TestRequest::default()
+                .insert_header((ACCEPT, accept))
+                .to_http_request();
+            let response = Negotiated::new(json!({ "id": 1 })).respond_to(&request);
+
+            assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), expected);
+            assert_eq!(response.headers().get(VARY).unwrap(), "Accept");
+        }
+
+        let request = TestRequest::default()
+            .insert_header((ACCEPT, "application/json;q=0, text/csv"))
+            .to_http_request();
+        let response = Negotiated::new(json!({ "id": 1 })).respond_to(&request);
+
+        assert_eq!(response.status(), 406);
+        assert_eq!(response.headers().get(VARY).unwrap(), "Accept");
+    }
+}
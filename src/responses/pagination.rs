@@ -1,2 +1,151 @@
+use std::fmt;
+use std::str::FromStr;
 
+use sea_orm::Order;
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PaginationSort {
+    Asc,
+    Desc,
+}
+
+impl From<PaginationSort> for Order {
+    fn from(value: PaginationSort) -> Self {
+        match value {
+            PaginationSort::Asc => Self::Asc,
+            PaginationSort::Desc => Self::Desc,
+        }
+    }
+}
+
+impl FromStr for PaginationSort {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(format!("unknown sort `{value}`")),
+        }
+    }
+}
+
+impl fmt::Display for PaginationSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Asc => f.write_str("asc"),
+            Self::Desc => f.write_str("desc"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PaginationSort {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct Pagination {
+    #[schema(example = 1, minimum = 1, required = false)]
+    #[param(example = 1, minimum = 1)]
+    page: Option<u64>,
+    #[schema(example = 10, minimum = 1, maximum = 1000, required = false)]
+    #[param(example = 10, minimum = 1, maximum = 1000)]
+    limit: Option<u64>,
+    #[schema()]
+    search: Option<String>,
+    #[schema()]
+    sort: Option<PaginationSort>,
+    #[schema(example = "createdAt")]
+    order: Option<String>,
+}
+
+impl Pagination {
+    pub fn new(page: u64, limit: u64) -> Self {
+        Self {
+            page: Some(page),
+            limit: Some(limit),
+            ..Default::default()
+        }
+    }
+
+    pub fn page(&self) -> u64 {
+        self.page.unwrap_or(1)
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit.unwrap_or(10).min(1000)
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.page().saturating_sub(1).saturating_mul(self.limit())
+    }
+
+    pub fn search(&self) -> Option<String> {
+        self.search.clone()
+    }
+
+    pub fn sort(&self) -> Order {
+        self.sort.unwrap_or(PaginationSort::Desc).into()
+    }
+
+    pub fn order(&self) -> Option<String> {
+        self.order.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::web::Query;
+
+    #[test]
+    fn defaults() {
+        let pagination = Query::<Pagination>::from_query("").unwrap();
+
+        assert_eq!(pagination.page(), 1);
+        assert_eq!(pagination.limit(), 10);
+        assert_eq!(pagination.offset(), 0);
+        assert!(pagination.search().is_none());
+        assert!(matches!(pagination.sort(), Order::Desc));
+        assert!(pagination.order().is_none());
+    }
+
+    #[test]
+    fn parse() {
+        let pagination =
+            Query::<Pagination>::from_query("page=3&limit=20&search=john&sort=ASC&order=name")
+                .unwrap();
+
+        assert_eq!(pagination.page(), 3);
+        assert_eq!(pagination.limit(), 20);
+        assert_eq!(pagination.offset(), 40);
+        assert_eq!(pagination.search().as_deref(), Some("john"));
+        assert!(matches!(pagination.sort(), Order::Asc));
+        assert_eq!(pagination.order().as_deref(), Some("name"));
+    }
+
+    #[test]
+    fn clamp() {
+        let pagination = Query::<Pagination>::from_query("limit=5000").unwrap();
+
+        assert_eq!(pagination.limit(), 1000);
+        assert!(Query::<Pagination>::from_query("sort=up").is_err());
+    }
+
+    #[test]
+    fn overflow() {
+        let pagination =
+            Query::<Pagination>::from_query("page=18446744073709551615&limit=1000").unwrap();
+
+        assert_eq!(pagination.offset(), u64::MAX);
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+use actix_web::body::BoxBody;
+use actix_web::{HttpRequest, HttpResponse, Responder, ResponseError};
+use serde_json::{json, Value};
+
+use super::error::Error;
+
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Problem(pub Error);
+
+impl Problem {
+    pub fn new<E: Into<Error>>(error: E) -> Self {
+        Self(error.into())
+    }
+
+    pub fn json(&self) -> Value {
+        let status = self.0.status_code();
+        let mut problem = json!({
+            "type": "about:blank",
+            "title": status.canonical_reason().unwrap_or("Unknown"),
+            "status": status.as_u16(),
+        });
+
+        if let Error::UnprocessableEntity { errors } = &self.0 {
+            let mut fields = errors.keys().collect::<Vec<_>>();
+            fields.sort();
+
+            let params = fields
+                .into_iter()
+                .flat_map(|field| {
+                    errors[field].iter().map(move |reason| {
+                        json!({
+                            "name": field,
+                            "reason": reason,
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            problem["detail"] = json!("The request contains invalid parameters");
+            problem["invalid-params"] = json!(params);
+        } else if let Some(message) = self.0.message() {
+            problem["detail"] = json!(message);
+        }
+
+        if let (Some(details), Some(problem)) = (self.0.details(), problem.as_object_mut()) {
+            problem.extend(details.clone());
+        }
+
+        problem
+    }
+
+    pub fn response(&self) -> HttpResponse {
+        HttpResponse::build(self.0.status_code())
+            .content_type(PROBLEM_CONTENT_TYPE)
+            .body(self.json().to_string())
+    }
+}
+
+impl From<Error> for Problem {
+    fn from(value: Error) -> Self {
+        Self(value)
+    }
+}
+
+impl Responder for Problem {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        self.response()
+    }
+}
+
+impl ResponseError for Problem {
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        self.response()
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.json())
+    }
+}
+
+impl fmt::Debug for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.json())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::responses::{NotFound, Validation};
+    use actix_web::body::to_bytes;
+    use actix_web::http::header::CONTENT_TYPE;
+
+    #[actix_web::test]
+    async fn message() {
+        let response = Problem::new(NotFound::new("User not found")).response();
+
+        assert_eq!(response.status(), 404);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            PROBLEM_CONTENT_TYPE
+        );
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "User not found",
+            })
+        );
+
+        let problem = Problem::new(Error::not_found_resource("user", 42));
+
+        assert_eq!(problem.json()["resource"], "user");
+        assert_eq!(problem.json()["id"], "42");
+    }
+
+    #[actix_web::test]
+    async fn invalid_params() {
+        let mut validation = Validation::new();
+        validation.add("password", "Password is required");
+        validation.add("email", "Email is invalid");
+
+        let response = Problem::new(validation).response();
+
+        assert_eq!(response.status(), 422);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            PROBLEM_CONTENT_TYPE
+        );
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body["status"], 422);
+        assert_eq!(
+            body["invalid-params"],
+            json!([
+                { "name": "email", "reason": "Email is invalid" },
+                { "name": "password", "reason": "Password is required" },
+            ])
+        );
+    }
+}
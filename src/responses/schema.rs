@@ -32,18 +32,18 @@ macro_rules! create {
             }
         }
 
-        impl Into<Error> for $name {
-            fn into(self) -> Error {
+        impl From<$name> for Error {
+            fn from(value: $name) -> Self {
                 Error::$name {
-                    message: self.message,
+                    message: value.message,
                 }
             }
         }
 
-        impl Into<Error> for &$name {
-            fn into(self) -> Error {
+        impl From<&$name> for Error {
+            fn from(value: &$name) -> Self {
                 Error::$name {
-                    message: self.message.clone(),
+                    message: value.message.clone(),
                 }
             }
         }
@@ -84,4 +84,10 @@ create!(BadRequest, 400, "Bad Request");
 create!(Unauthorized, 401, "Unauthorized");
 create!(Forbidden, 403, "Forbidden");
 create!(NotFound, 404, "Not Found");
+create!(Gone, 410, "Gone");
+create!(PayloadTooLarge, 413, "Payload Too Large");
 create!(InternalServerError, 500, "Internal Server Error");
+create!(NotImplemented, 501, "Not Implemented");
+create!(BadGateway, 502, "Bad Gateway");
+create!(ServiceUnavailable, 503, "Service Unavailable");
+create!(GatewayTimeout, 504, "Gateway Timeout");
@@ -1,8 +1,17 @@
-use std::{collections::HashMap, fmt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+};
 
-use actix_web::{body::BoxBody, HttpRequest, HttpResponse, Responder, ResponseError};
-use serde::{Deserialize, Serialize};
-use serde_json::json;
+use actix_web::{
+    body::BoxBody, dev::Payload, web::Json, FromRequest, HttpRequest, HttpResponse, Responder,
+    ResponseError,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
 use utoipa::{
     openapi::{ObjectBuilder, RefOr, Schema, SchemaType},
     IntoResponses, ToSchema,
@@ -26,10 +35,43 @@ impl Validation {
     pub fn add<F: ToString, M: ToString>(&mut self, field: F, message: M) {
         self.errors
             .entry(field.to_string())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(message.to_string());
     }
 
+    pub fn add_indexed<F: ToString, M: ToString>(&mut self, field: F, index: usize, message: M) {
+        self.add(format!("{}[{}]", field.to_string(), index), message);
+    }
+
+    pub fn add_nested<P: ToString, C: ToString, M: ToString>(
+        &mut self,
+        parent: P,
+        child: C,
+        message: M,
+    ) {
+        self.add(
+            format!("{}.{}", parent.to_string(), child.to_string()),
+            message,
+        );
+    }
+
+    pub fn with_prefix<T: ToString>(&self, prefix: T) -> Self {
+        let prefix = prefix.to_string();
+        let errors = self
+            .errors
+            .iter()
+            .map(|(field, messages)| (format!("{prefix}.{field}"), messages.clone()))
+            .collect();
+
+        Self { errors }
+    }
+
+    pub fn check<F: ToString, M: ToString>(&mut self, condition: bool, field: F, message: M) {
+        if !condition {
+            self.add(field, message);
+        }
+    }
+
     pub fn get<T: ToString>(&self, field: T) -> Vec<String> {
         self.errors
             .get(&field.to_string())
@@ -37,6 +79,10 @@ impl Validation {
             .unwrap_or_default()
     }
 
+    pub fn json(&self) -> Value {
+        json!(self)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.errors.is_empty()
     }
@@ -44,6 +90,20 @@ impl Validation {
     pub fn has_error<T: ToString>(&self, field: T) -> bool {
         !self.get(field).is_empty()
     }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Default for Validation {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl From<Error> for Validation {
@@ -52,22 +112,66 @@ impl From<Error> for Validation {
             return Self { errors };
         }
 
-        return Self::new();
+        Self::new()
     }
 }
 
-impl Into<Error> for &Validation {
-    fn into(self) -> Error {
+#[cfg(feature = "validator")]
+impl From<validator::ValidationErrors> for Validation {
+    fn from(value: validator::ValidationErrors) -> Self {
+        let mut validation = Self::new();
+
+        flatten(&mut validation, None, &value);
+
+        validation
+    }
+}
+
+#[cfg(feature = "validator")]
+fn flatten(
+    validation: &mut Validation,
+    parent: Option<&str>,
+    errors: &validator::ValidationErrors,
+) {
+    use validator::ValidationErrorsKind::*;
+
+    for (field, kind) in errors.errors() {
+        let field = match parent {
+            Some(parent) => format!("{parent}.{field}"),
+            None => field.to_string(),
+        };
+
+        match kind {
+            Field(errors) => {
+                for error in errors {
+                    match &error.message {
+                        Some(message) => validation.add(&field, message),
+                        None => validation.add(&field, &error.code),
+                    }
+                }
+            }
+            Struct(errors) => flatten(validation, Some(&field), errors),
+            List(items) => {
+                for (index, errors) in items {
+                    flatten(validation, Some(&format!("{field}[{index}]")), errors);
+                }
+            }
+        }
+    }
+}
+
+impl From<&Validation> for Error {
+    fn from(value: &Validation) -> Self {
         Error::UnprocessableEntity {
-            errors: self.errors.clone(),
+            errors: value.errors.clone(),
         }
     }
 }
 
-impl Into<Error> for Validation {
-    fn into(self) -> Error {
+impl From<Validation> for Error {
+    fn from(value: Validation) -> Self {
         Error::UnprocessableEntity {
-            errors: self.errors,
+            errors: value.errors,
         }
     }
 }
@@ -90,7 +194,7 @@ impl Responder for Validation {
 
 impl fmt::Display for Validation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", json!(self))
+        write!(f, "{}", self.json())
     }
 }
 
@@ -129,3 +233,276 @@ impl ToSchema<'_> for Validation {
         ("Validation", schema.into())
     }
 }
+
+pub trait Validatable {
+    fn validate(&self) -> Result<(), Validation>;
+}
+
+#[derive(Clone, Debug)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> ValidatedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ValidatedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned + Validatable + 'static> FromRequest for ValidatedJson<T> {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(request: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = Json::<T>::from_request(request, payload);
+
+        Box::pin(async move {
+            let value = json.await?.into_inner();
+
+            value.validate()?;
+
+            Ok(Self(value))
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BatchValidation {
+    pub errors: BTreeMap<usize, BTreeMap<String, Vec<String>>>,
+}
+
+impl BatchValidation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, index: usize, validation: Validation) {
+        if !validation.is_empty() {
+            self.errors
+                .insert(index, validation.errors.into_iter().collect());
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<Validation> {
+        self.errors.get(&index).map(|errors| Validation {
+            errors: errors.clone().into_iter().collect(),
+        })
+    }
+
+    pub fn json(&self) -> Value {
+        json!(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl ResponseError for BatchValidation {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::UNPROCESSABLE_ENTITY
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        HttpResponse::UnprocessableEntity().json(self.json())
+    }
+}
+
+impl Responder for BatchValidation {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        self.error_response()
+    }
+}
+
+impl fmt::Display for BatchValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.json())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn passes() {
+        let mut validation = Validation::new();
+        validation.check(true, "email", "Email is required");
+        validation.check(true, "password", "Password is required");
+
+        assert!(validation.into_result().is_ok());
+    }
+
+    #[test]
+    fn fails() {
+        let mut validation = Validation::new();
+        validation.check(true, "email", "Email is required");
+        validation.check(false, "password", "Password is required");
+
+        let validation = validation.into_result().unwrap_err();
+
+        assert!(!validation.has_error("email"));
+        assert_eq!(validation.get("password"), vec!["Password is required"]);
+    }
+
+    #[test]
+    fn keys() {
+        let mut validation = Validation::new();
+        validation.add_indexed("tags", 1, "Tag is too long");
+        validation.add_nested("address", "city", "City is required");
+        validation.add_nested("items[2]", "price", "Price must be positive");
+
+        assert!(validation.has_error("tags[1]"));
+        assert!(validation.has_error("address.city"));
+        assert!(validation.has_error("items[2].price"));
+    }
+
+    #[test]
+    fn prefix() {
+        let mut item = Validation::new();
+        item.add("price", "Price must be positive");
+        item.add_indexed("options", 0, "Option is invalid");
+
+        let validation = item.with_prefix("items[2]");
+
+        assert_eq!(
+            validation.get("items[2].price"),
+            vec!["Price must be positive"]
+        );
+        assert_eq!(
+            validation.get("items[2].options[0]"),
+            vec!["Option is invalid"]
+        );
+        assert!(!validation.has_error("price"));
+    }
+
+    #[test]
+    fn json() {
+        let mut validation = Validation::new();
+        validation.add("email", "Email is required");
+
+        let error: Error = validation.clone().into();
+
+        assert_eq!(
+            validation.json(),
+            json!({ "errors": { "email": ["Email is required"] } })
+        );
+        assert_eq!(validation.json(), error.json());
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn validator() {
+        use validator::{ValidationError, ValidationErrors};
+
+        let mut address = ValidationErrors::new();
+        address.add("city", ValidationError::new("required"));
+
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "email",
+            ValidationError::new("email").with_message("Email is invalid".into()),
+        );
+        errors.add("password", ValidationError::new("length"));
+        errors.add("password", ValidationError::new("uppercase"));
+        errors.merge_self("address", Err(address));
+
+        let validation = Validation::from(errors);
+
+        assert_eq!(validation.get("email"), vec!["Email is invalid"]);
+        assert_eq!(validation.get("password"), vec!["length", "uppercase"]);
+        assert_eq!(validation.get("address.city"), vec!["required"]);
+    }
+
+    #[derive(Deserialize)]
+    struct Register {
+        email: String,
+    }
+
+    impl Validatable for Register {
+        fn validate(&self) -> Result<(), Validation> {
+            let mut validation = Validation::new();
+
+            validation.check(self.email.contains('@'), "email", "Email is invalid");
+            validation.into_result()
+        }
+    }
+
+    #[actix_web::test]
+    async fn validated_json() {
+        use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+        use actix_web::{web, App};
+
+        let app = init_service(App::new().route(
+            "/",
+            web::post().to(|register: ValidatedJson<Register>| async move {
+                HttpResponse::Ok().body(register.into_inner().email)
+            }),
+        ))
+        .await;
+        let request = TestRequest::post()
+            .uri("/")
+            .set_json(json!({ "email": "john@example.com" }))
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 200);
+
+        let request = TestRequest::post()
+            .uri("/")
+            .set_json(json!({ "email": "john" }))
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 422);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(body, json!({ "errors": { "email": ["Email is invalid"] } }));
+    }
+
+    #[actix_web::test]
+    async fn batch() {
+        let mut batch = BatchValidation::new();
+
+        for (index, name) in ["", "John", "Jane", ""].iter().enumerate() {
+            let mut validation = Validation::new();
+
+            validation.check(!name.is_empty(), "name", "Name is required");
+            validation.check(!name.is_empty(), "email", "Email is required");
+            batch.add(index, validation);
+        }
+
+        assert!(batch.get(1).is_none());
+        assert!(batch.get(0).unwrap().has_error("name"));
+        assert_eq!(
+            serde_json::to_string(&batch).unwrap(),
+            concat!(
+                r#"{"errors":{"#,
+                r#""0":{"email":["Email is required"],"name":["Name is required"]},"#,
+                r#""3":{"email":["Email is required"],"name":["Name is required"]}}}"#,
+            )
+        );
+
+        let request = actix_web::test::TestRequest::default().to_http_request();
+        let response = batch.respond_to(&request);
+
+        assert_eq!(response.status(), 422);
+    }
+}
@@ -0,0 +1,126 @@
+use actix_web::http::Method;
+use actix_web::web::{self, ServiceConfig};
+use actix_web::{App, FromRequest, Handler, Responder};
+
+pub struct Routes<'a> {
+    config: &'a mut ServiceConfig,
+    routes: Vec<(Method, String)>,
+}
+
+impl<'a> Routes<'a> {
+    pub fn new(config: &'a mut ServiceConfig) -> Self {
+        Self {
+            config,
+            routes: vec![],
+        }
+    }
+
+    pub fn route<F, Args>(&mut self, method: Method, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.routes.push((method.clone(), path.to_string()));
+        self.config.route(path, web::method(method).to(handler));
+        self
+    }
+
+    pub fn get<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::GET, path, handler)
+    }
+
+    pub fn post<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::POST, path, handler)
+    }
+
+    pub fn put<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::PUT, path, handler)
+    }
+
+    pub fn patch<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::PATCH, path, handler)
+    }
+
+    pub fn delete<F, Args>(&mut self, path: &str, handler: F) -> &mut Self
+    where
+        F: Handler<Args>,
+        Args: FromRequest + 'static,
+        F::Output: Responder + 'static,
+    {
+        self.route(Method::DELETE, path, handler)
+    }
+
+    pub fn routes(&self) -> &[(Method, String)] {
+        &self.routes
+    }
+}
+
+pub fn collect<F>(callback: F) -> Vec<(Method, String)>
+where
+    F: FnOnce(&mut Routes),
+{
+    let mut routes = vec![];
+
+    App::new().configure(|config| {
+        let mut recorder = Routes::new(config);
+
+        callback(&mut recorder);
+        routes = recorder.routes;
+    });
+
+    routes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::HttpResponse;
+
+    fn users(routes: &mut Routes) {
+        routes
+            .get("/users", HttpResponse::Ok)
+            .delete("/users/{id}", HttpResponse::NoContent);
+    }
+
+    #[test]
+    fn collected() {
+        assert_eq!(
+            collect(users),
+            vec![
+                (Method::GET, "/users".to_string()),
+                (Method::DELETE, "/users/{id}".to_string()),
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn registered() {
+        let app =
+            init_service(App::new().configure(|config| users(&mut Routes::new(config)))).await;
+        let request = TestRequest::delete().uri("/users/1").to_request();
+
+        assert_eq!(call_service(&app, request).await.status(), 204);
+    }
+}
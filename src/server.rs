@@ -1,31 +1,59 @@
 use std::env;
-use std::io::Error;
+use std::future::Future;
+use std::io::{Error, ErrorKind};
 use std::net::SocketAddr;
-use std::usize::MAX;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
 
 use actix_cors::Cors;
-use actix_web::dev;
+use actix_web::body::MessageBody;
+use actix_web::dev::{self, ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::error::{JsonPayloadError, PathError, QueryPayloadError, UrlencodedError};
+use actix_web::http::Method;
+use actix_web::middleware::Condition;
 // use actix_web::middleware::{NormalizePath, TrailingSlash};
-use actix_web::web::{Data, FormConfig, JsonConfig, PathConfig, PayloadConfig, ServiceConfig};
-use actix_web::{App, HttpServer};
+use actix_web::web::{
+    Data, FormConfig, JsonConfig, PathConfig, PayloadConfig, Query, QueryConfig, ServiceConfig,
+};
+use actix_web::{App, HttpRequest, HttpServer};
 use rustls::ServerConfig;
 use sea_orm::DatabaseConnection;
 
 use crate::database;
+use crate::health::Health;
+use crate::middleware::{Compression, ConcurrencyLimit, SecurityHeaders};
+use crate::responses::{self, PayloadTooLarge, Validation};
+use crate::routes::{self, Routes};
 
 #[derive(Clone)]
 pub struct Server {
     port: u16,
+    http_port: u16,
     database: DatabaseConnection,
     tls: Option<ServerConfig>,
+    limit: usize,
+    compression: Option<Compression>,
+    health: Option<Health>,
+    backlog: Option<u32>,
+    max_connections: Option<usize>,
+    concurrency: Option<ConcurrencyLimit>,
+    security_headers: Option<SecurityHeaders>,
 }
 
 impl Server {
     pub fn new(port: u16, database: DatabaseConnection) -> Self {
         Self {
             port,
+            http_port: 80,
             database,
             tls: None,
+            limit: usize::MAX,
+            compression: None,
+            health: None,
+            backlog: None,
+            max_connections: None,
+            concurrency: None,
+            security_headers: None,
         }
     }
 
@@ -39,8 +67,16 @@ impl Server {
 
         Self {
             port,
+            http_port: 80,
             database: database.unwrap(),
             tls: None,
+            limit: usize::MAX,
+            compression: None,
+            health: None,
+            backlog: None,
+            max_connections: None,
+            concurrency: None,
+            security_headers: None,
         }
     }
 
@@ -48,6 +84,10 @@ impl Server {
         self.port = port;
     }
 
+    pub fn http_port(&mut self, port: u16) {
+        self.http_port = port;
+    }
+
     pub fn database(&mut self, database: DatabaseConnection) {
         self.database = database;
     }
@@ -56,81 +96,741 @@ impl Server {
         self.tls = Some(tls);
     }
 
-    pub fn run<F>(self, callback: F) -> Result<dev::Server, Error>
+    pub fn limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    pub fn compress(&mut self, compress: bool) {
+        self.compression = compress.then(Compression::default);
+    }
+
+    pub fn compression(&mut self, compression: Compression) {
+        self.compression = Some(compression);
+    }
+
+    pub fn health(&mut self, mut health: Health) {
+        health.add(self.database.clone());
+        self.health = Some(health);
+    }
+
+    pub fn backlog(&mut self, backlog: u32) {
+        self.backlog = Some(backlog);
+    }
+
+    pub fn max_connections(&mut self, max_connections: usize) {
+        self.max_connections = Some(max_connections);
+    }
+
+    pub fn concurrency(&mut self, permits: usize) {
+        self.concurrency = Some(ConcurrencyLimit::new(permits));
+    }
+
+    pub fn security_headers(&mut self, headers: SecurityHeaders) {
+        self.security_headers = Some(headers);
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.backlog == Some(0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "backlog must be greater than 0",
+            ));
+        }
+
+        if self.max_connections == Some(0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "max_connections must be greater than 0",
+            ));
+        }
+
+        if matches!(&self.concurrency, Some(limit) if limit.available() == 0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "concurrency must be greater than 0",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn build_app<F>(
+        &self,
+        callback: F,
+    ) -> App<
+        impl ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse<impl MessageBody>,
+            Error = actix_web::Error,
+            InitError = (),
+        >,
+    >
     where
-        F: FnOnce(&mut ServiceConfig) -> () + Clone + Copy + Send + 'static,
+        F: FnOnce(&mut ServiceConfig),
     {
-        if self.tls.is_some() {
-            return self.run_tls(callback);
-        }
+        let payload = PayloadConfig::new(self.limit);
+        let path = PathConfig::default().error_handler(Server::path_error);
+        let query = QueryConfig::default().error_handler(Server::query_error);
+        let json = JsonConfig::default()
+            .limit(self.limit)
+            .error_handler(Server::json_error);
+        let form = FormConfig::default()
+            .limit(self.limit)
+            .error_handler(Server::form_error);
+
+        let security_headers = self
+            .security_headers
+            .clone()
+            .unwrap_or_else(|| SecurityHeaders::new(self.tls.is_some()));
+
+        App::new()
+            // .wrap(NormalizePath::new(TrailingSlash::Trim))
+            .wrap(Condition::new(
+                self.concurrency.is_some(),
+                self.concurrency
+                    .clone()
+                    .unwrap_or_else(|| ConcurrencyLimit::new(0)),
+            ))
+            .wrap(Server::cors())
+            .wrap(Condition::new(
+                self.compression.is_some(),
+                self.compression.clone().unwrap_or_default(),
+            ))
+            .wrap(security_headers)
+            .app_data(payload)
+            .app_data(path)
+            .app_data(query)
+            .app_data(json)
+            .app_data(form)
+            .app_data(Data::new(self.database.clone()))
+            .configure(|config| {
+                if let Some(health) = &self.health {
+                    health.configure(config);
+                }
+            })
+            .configure(callback)
+    }
+
+    pub fn run<F>(self, callback: F) -> Result<Instance, Error>
+    where
+        F: FnOnce(&mut ServiceConfig) + Clone + Copy + Send + 'static,
+    {
+        self.validate()?;
 
         let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
-        let database = self.database.clone();
-        let factory = move || {
-            let payload = PayloadConfig::new(MAX);
-            let path = PathConfig::default();
-            let json = JsonConfig::default().limit(MAX);
-            let form = FormConfig::default().limit(MAX);
+        let app = self.clone();
+        let factory = move || app.build_app(callback);
+        let http = |factory| {
+            let mut server = HttpServer::new(factory).workers(4);
 
-            App::new()
-                // .wrap(NormalizePath::new(TrailingSlash::Trim))
-                .wrap(Server::cors())
-                .app_data(payload)
-                .app_data(path)
-                .app_data(json)
-                .app_data(form)
-                .app_data(Data::new(database.clone()))
-                .configure(callback)
+            if let Some(backlog) = self.backlog {
+                server = server.backlog(backlog);
+            }
+
+            if let Some(max_connections) = self.max_connections {
+                server = server.max_connections(max_connections);
+            }
+
+            server
+        };
+
+        let Some(tls) = self.tls.clone() else {
+            let server = http(factory).bind(addr)?;
+            let addrs = server.addrs();
+
+            return Ok(Instance {
+                server: server.run(),
+                companion: None,
+                addrs,
+            });
         };
 
-        let server = HttpServer::new(factory).workers(4).bind(addr)?.run();
+        let plain = http(factory.clone()).bind(SocketAddr::from(([0, 0, 0, 0], self.http_port)))?;
+        let server = http(factory).bind_rustls_021(addr, tls)?;
+        let mut addrs = server.addrs();
 
-        Ok(server)
+        addrs.extend(plain.addrs());
+
+        let plain = plain.run();
+        let companion = plain.handle();
+
+        actix::spawn(plain);
+
+        Ok(Instance {
+            server: server.run(),
+            companion: Some(companion),
+            addrs,
+        })
     }
 
-    fn run_tls<F>(self, callback: F) -> Result<dev::Server, Error>
+    pub fn routes<F>(callback: F) -> Vec<(Method, String)>
     where
-        F: FnOnce(&mut ServiceConfig) -> () + Clone + Copy + Send + 'static,
+        F: FnOnce(&mut Routes),
     {
-        let addr = SocketAddr::from(([0, 0, 0, 0], self.port));
-        let database = self.database.clone();
-        let tls = self.tls.unwrap();
-        let factory = move || {
-            let payload = PayloadConfig::new(MAX);
-            let path = PathConfig::default();
-            let json = JsonConfig::default().limit(MAX);
-            let form = FormConfig::default().limit(MAX);
+        routes::collect(callback)
+    }
 
-            App::new()
-                // .wrap(NormalizePath::new(TrailingSlash::Trim))
-                .wrap(Server::cors())
-                .app_data(payload)
-                .app_data(path)
-                .app_data(json)
-                .app_data(form)
-                .app_data(Data::new(database.clone()))
-                .configure(callback)
+    pub fn cors() -> Cors {
+        Cors::permissive()
+    }
+
+    pub fn json_error(error: JsonPayloadError, _: &HttpRequest) -> actix_web::Error {
+        match error {
+            JsonPayloadError::Overflow { limit }
+            | JsonPayloadError::OverflowKnownLength { limit, .. } => Server::overflow(limit),
+            JsonPayloadError::Deserialize(error) => Server::deserialize(error),
+            _ => error.into(),
+        }
+    }
+
+    pub fn form_error(error: UrlencodedError, _: &HttpRequest) -> actix_web::Error {
+        match error {
+            UrlencodedError::Overflow { limit, .. } => Server::overflow(limit),
+            _ => error.into(),
+        }
+    }
+
+    pub fn path_error(error: PathError, request: &HttpRequest) -> actix_web::Error {
+        let PathError::Deserialize(error) = error else {
+            return error.into();
+        };
+        let message = error.to_string();
+        let segment = request
+            .match_info()
+            .iter()
+            .find(|(_, value)| message.contains(&format!("{:?}", value)))
+            .map(|(name, _)| name);
+        let message = match segment {
+            Some(segment) => format!("Invalid path segment `{}`: {}", segment, message),
+            None => format!("Invalid path: {}", message),
         };
 
-        let f = factory.clone();
+        responses::Error::BadRequest { message }.into()
+    }
+
+    pub fn query_error(error: QueryPayloadError, request: &HttpRequest) -> actix_web::Error {
+        let QueryPayloadError::Deserialize(error) = error else {
+            return error.into();
+        };
+        let message = error.to_string();
+        let field = Server::field(&message)
+            .map(str::to_string)
+            .or_else(|| Server::query_key(&message, request.query_string()));
+
+        match field {
+            Some(field) => {
+                let mut validation = Validation::new();
+
+                validation.add(&field, &message);
+                validation.into()
+            }
+            None => responses::Error::BadRequest {
+                message: format!("Invalid query string: {}", message),
+            }
+            .into(),
+        }
+    }
+
+    fn deserialize(error: serde_json::Error) -> actix_web::Error {
+        let message = error.to_string();
+        let message = message.split(" at line ").next().unwrap_or_default();
+        let mut validation = Validation::new();
+
+        validation.add(Server::field(message).unwrap_or("body"), message);
+        validation.into()
+    }
+
+    fn field(message: &str) -> Option<&str> {
+        match message.split('`').nth(1) {
+            Some(field) if message.starts_with("missing field") => Some(field),
+            Some(field) if message.starts_with("unknown field") => Some(field),
+            _ => None,
+        }
+    }
+
+    fn query_key(message: &str, query: &str) -> Option<String> {
+        let pairs = Query::<Vec<(String, String)>>::from_query(query).ok()?;
+        let quoted = message.split('`').nth(1);
+
+        pairs
+            .into_inner()
+            .into_iter()
+            .find(|(_, value)| {
+                let errors = [
+                    value.parse::<u128>().err().map(|e| e.to_string()),
+                    value.parse::<i128>().err().map(|e| e.to_string()),
+                    value.parse::<f64>().err().map(|e| e.to_string()),
+                    value.parse::<bool>().err().map(|e| e.to_string()),
+                ];
+
+                quoted == Some(value.as_str()) || errors.iter().flatten().any(|e| e == message)
+            })
+            .map(|(key, _)| key)
+    }
+
+    fn overflow(limit: usize) -> actix_web::Error {
+        PayloadTooLarge::new(format!("Payload exceeds the limit of {} bytes", limit)).into()
+    }
+}
+
+pub struct Instance {
+    server: dev::Server,
+    companion: Option<dev::ServerHandle>,
+    addrs: Vec<SocketAddr>,
+}
+
+impl Instance {
+    pub fn addr(&self) -> SocketAddr {
+        self.addrs[0]
+    }
+
+    pub fn addrs(&self) -> &[SocketAddr] {
+        &self.addrs
+    }
+
+    pub fn handle(&self) -> dev::ServerHandle {
+        self.server.handle()
+    }
+
+    pub async fn stop(&self, graceful: bool) {
+        if let Some(companion) = &self.companion {
+            companion.stop(graceful).await;
+        }
+
+        self.server.handle().stop(graceful).await
+    }
+}
+
+impl Future for Instance {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = ready!(Pin::new(&mut self.server).poll(cx));
+
+        if let Some(companion) = self.companion.take() {
+            actix::spawn(async move { companion.stop(true).await });
+        }
+
+        Poll::Ready(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::web::{self, Json, Path, Query};
+    use serde_json::{json, Value};
+
+    use crate::responses::{Pagination, Success};
+
+    #[actix_web::test]
+    async fn stop() {
+        let server = Server::new(0, DatabaseConnection::Disconnected)
+            .run(|_| {})
+            .unwrap();
+        let handle = server.handle();
+        let running = actix::spawn(server);
+
+        handle.stop(true).await;
+
+        assert!(running.await.unwrap().is_ok());
+    }
+
+    #[actix_web::test]
+    async fn ephemeral_port() {
+        let server = Server::new(0, DatabaseConnection::Disconnected)
+            .run(|_| {})
+            .unwrap();
 
-        actix::spawn(async move {
-            HttpServer::new(f)
-                .workers(4)
-                .bind(SocketAddr::from(([0, 0, 0, 0], 80)))?
-                .run()
-                .await
+        let addr = server.addr();
+        let handle = server.handle();
+        let running = actix::spawn(server);
+
+        assert_ne!(addr.port(), 0);
+
+        handle.stop(false).await;
+
+        assert!(running.await.unwrap().is_ok());
+    }
+
+    #[actix_web::test]
+    async fn payload_too_large() {
+        let app = init_service(
+            App::new()
+                .app_data(
+                    JsonConfig::default()
+                        .limit(16)
+                        .error_handler(Server::json_error),
+                )
+                .route(
+                    "/",
+                    web::post().to(|_: Json<Value>| async { Success::empty() }),
+                ),
+        )
+        .await;
+        let request = TestRequest::post()
+            .uri("/")
+            .set_json(json!({ "name": "a very long name indeed" }))
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 413);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(
+            body,
+            json!({ "message": "Payload exceeds the limit of 16 bytes" })
+        );
+    }
+
+    #[actix_web::test]
+    async fn unprocessable_json() {
+        #[derive(serde::Deserialize)]
+        struct User {
+            #[allow(dead_code)]
+            name: String,
+        }
+
+        let app = init_service(
+            App::new()
+                .app_data(JsonConfig::default().error_handler(Server::json_error))
+                .route(
+                    "/",
+                    web::post().to(|_: Json<User>| async { Success::empty() }),
+                ),
+        )
+        .await;
+        let request = TestRequest::post()
+            .uri("/")
+            .set_json(json!({ "email": "john@example.com" }))
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 422);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(
+            body,
+            json!({ "errors": { "name": ["missing field `name`"] } })
+        );
+
+        let request = TestRequest::post()
+            .uri("/")
+            .insert_header(("content-type", "application/json"))
+            .set_payload("{\"name\":")
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 422);
+
+        let body: Value = read_body_json(response).await;
+
+        assert!(body["errors"]["body"].is_array());
+    }
+
+    #[actix_web::test]
+    async fn invalid_path() {
+        let app = init_service(
+            App::new()
+                .app_data(PathConfig::default().error_handler(Server::path_error))
+                .route(
+                    "/users/{id}",
+                    web::get().to(|_: Path<u64>| async { Success::empty() }),
+                ),
+        )
+        .await;
+        let request = TestRequest::get().uri("/users/abc").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 400);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(
+            body,
+            json!({ "message": "Invalid path segment `id`: can not parse \"abc\" to a u64" })
+        );
+    }
+
+    #[actix_web::test]
+    async fn invalid_query() {
+        let app = init_service(
+            App::new()
+                .app_data(QueryConfig::default().error_handler(Server::query_error))
+                .route(
+                    "/",
+                    web::get().to(|_: Query<Pagination>| async { Success::empty() }),
+                ),
+        )
+        .await;
+        let request = TestRequest::get().uri("/?limit=abc").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 422);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(
+            body,
+            json!({ "errors": { "limit": ["invalid digit found in string"] } })
+        );
+
+        let request = TestRequest::get()
+            .uri("/?page=2&limit=10&sort=up")
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 422);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(body, json!({ "errors": { "sort": ["unknown sort `up`"] } }));
+
+        #[derive(serde::Deserialize)]
+        struct Search {
+            #[allow(dead_code)]
+            keyword: String,
+        }
+
+        let app = init_service(
+            App::new()
+                .app_data(QueryConfig::default().error_handler(Server::query_error))
+                .route(
+                    "/",
+                    web::get().to(|_: Query<Search>| async { Success::empty() }),
+                ),
+        )
+        .await;
+        let request = TestRequest::get().uri("/?limit=10").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), 422);
+
+        let body: Value = read_body_json(response).await;
+
+        assert_eq!(
+            body,
+            json!({ "errors": { "keyword": ["missing field `keyword`"] } })
+        );
+    }
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    fn get_tls(addr: SocketAddr, path: &str) -> String {
+        use std::io::{BufReader, Read, Write};
+        use std::net::TcpStream;
+        use std::sync::Arc;
+
+        use rustls::{Certificate, ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+
+        let file = &mut BufReader::new(std::fs::File::open(fixture("cert.pem")).unwrap());
+        let mut roots = RootCertStore::empty();
+
+        for cert in rustls_pemfile::certs(file).unwrap() {
+            roots.add(&Certificate(cert)).unwrap();
+        }
+
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connection =
+            ClientConnection::new(Arc::new(config), "localhost".try_into().unwrap()).unwrap();
+        let mut stream = StreamOwned::new(connection, TcpStream::connect(addr).unwrap());
+        let mut response = String::new();
+
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        )
+        .unwrap();
+        stream.read_to_string(&mut response).ok();
+
+        response
+    }
+
+    #[actix_web::test]
+    async fn tls() {
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.tls(crate::tls::configure(
+            fixture("cert.pem"),
+            fixture("key.pem"),
+        ));
+        server.http_port(0);
+        server.backlog(128);
+        server.max_connections(1024);
+
+        let server = server
+            .run(|config| {
+                config.route("/users", web::get().to(|| async { Success::empty() }));
+            })
+            .unwrap();
+        let addrs = server.addrs().to_vec();
+        let handle = server.handle();
+        let running = actix::spawn(server);
+
+        assert_eq!(addrs.len(), 2);
+
+        let addr = SocketAddr::from(([127, 0, 0, 1], addrs[0].port()));
+        let secure = actix_web::rt::task::spawn_blocking(move || {
+            (get_tls(addr, "/users"), get_tls(addr, "/missing"))
         });
+        let (found, missing) = secure.await.unwrap();
+
+        assert!(found.starts_with("HTTP/1.1 200"));
+        assert!(missing.starts_with("HTTP/1.1 404"));
+        assert!(found.contains("strict-transport-security: max-age=31536000"));
 
-        let server = HttpServer::new(factory)
-            .workers(4)
-            .bind_rustls_021(addr, tls)?
-            .run();
+        let response = awc::Client::new()
+            .get(format!("http://127.0.0.1:{}/users", addrs[1].port()))
+            .send()
+            .await
+            .unwrap();
 
-        Ok(server)
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get("x-content-type-options").unwrap(),
+            "nosniff"
+        );
+
+        handle.stop(false).await;
+
+        assert!(running.await.unwrap().is_ok());
     }
 
-    pub fn cors() -> Cors {
-        Cors::permissive()
+    #[cfg(feature = "compress-gzip")]
+    #[actix_web::test]
+    async fn compress() {
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.compress(true);
+
+        let server = server
+            .run(|config| {
+                config.route("/", web::get().to(|| async { "lighter ".repeat(1024) }));
+            })
+            .unwrap();
+        let url = format!("http://127.0.0.1:{}/", server.addr().port());
+        let handle = server.handle();
+        let running = actix::spawn(server);
+        let response = awc::Client::new()
+            .get(url)
+            .insert_header(("accept-encoding", "gzip"))
+            .no_decompress()
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+
+        handle.stop(false).await;
+
+        assert!(running.await.unwrap().is_ok());
+    }
+
+    #[actix_web::test]
+    async fn health() {
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.health(Health::new());
+
+        let server = server
+            .run(|config| {
+                config.route("/users", web::get().to(|| async { Success::empty() }));
+            })
+            .unwrap();
+        let url = format!("http://127.0.0.1:{}", server.addr().port());
+        let handle = server.handle();
+        let running = actix::spawn(server);
+        let client = awc::Client::new();
+        let health = client.get(format!("{url}/health")).send().await.unwrap();
+        let ready = client.get(format!("{url}/ready")).send().await.unwrap();
+        let users = client.get(format!("{url}/users")).send().await.unwrap();
+
+        assert_eq!(health.status(), 200);
+        assert_eq!(ready.status(), 503);
+        assert_eq!(users.status(), 200);
+
+        handle.stop(false).await;
+
+        assert!(running.await.unwrap().is_ok());
+    }
+
+    #[actix_web::test]
+    async fn connections() {
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.backlog(0);
+
+        let error = server.run(|_| {}).err().unwrap();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.max_connections(0);
+
+        assert!(server.run(|_| {}).is_err());
+
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.concurrency(0);
+
+        assert!(server.run(|_| {}).is_err());
+
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.backlog(128);
+        server.max_connections(1024);
+
+        let server = server.run(|_| {}).unwrap();
+        let handle = server.handle();
+        let running = actix::spawn(server);
+
+        handle.stop(false).await;
+
+        assert!(running.await.unwrap().is_ok());
+    }
+
+    #[actix_web::test]
+    async fn build_app() {
+        let mut server = Server::new(0, DatabaseConnection::Disconnected);
+
+        server.limit(16);
+        server.health(Health::new());
+        server.concurrency(8);
+
+        let app = init_service(server.build_app(|config| {
+            config.route(
+                "/users",
+                web::post().to(|_: Json<Value>| async { Success::empty() }),
+            );
+        }))
+        .await;
+        let request = TestRequest::get().uri("/health").to_request();
+
+        assert_eq!(call_service(&app, request).await.status(), 200);
+
+        let request = TestRequest::post()
+            .uri("/users")
+            .set_json(json!({ "name": "John" }))
+            .to_request();
+
+        assert_eq!(call_service(&app, request).await.status(), 200);
+
+        let request = TestRequest::post()
+            .uri("/users")
+            .set_json(json!({ "name": "a very long name indeed" }))
+            .to_request();
+
+        assert_eq!(call_service(&app, request).await.status(), 413);
     }
 }
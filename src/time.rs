@@ -1,7 +1,9 @@
-use chrono::{NaiveDateTime, ParseError, Utc};
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, ParseError, Utc, Weekday};
+#[cfg(feature = "db")]
+use sea_orm::Value;
 
 pub fn now() -> NaiveDateTime {
-    Utc::now().naive_local()
+    Utc::now().naive_utc()
 }
 
 pub fn unix() -> u64 {
@@ -23,6 +25,84 @@ pub fn format(value: NaiveDateTime) -> String {
     value.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
+#[cfg(feature = "db")]
+pub fn to_db(value: NaiveDateTime) -> Value {
+    Value::ChronoDateTime(Some(Box::new(value)))
+}
+
+#[cfg(feature = "db")]
+pub fn from_db(value: Value) -> Option<NaiveDateTime> {
+    match value {
+        Value::ChronoDateTime(Some(value)) => Some(*value),
+        Value::ChronoDateTimeUtc(Some(value)) => Some(value.naive_utc()),
+        Value::ChronoDateTimeLocal(Some(value)) => Some(value.naive_utc()),
+        Value::ChronoDateTimeWithTimeZone(Some(value)) => Some(value.naive_utc()),
+        Value::BigInt(Some(value)) => from_unix(value),
+        _ => None,
+    }
+}
+
+pub fn start_of_day(value: NaiveDateTime) -> NaiveDateTime {
+    value.date().and_time(NaiveTime::MIN)
+}
+
+pub fn end_of_day(value: NaiveDateTime) -> NaiveDateTime {
+    value
+        .date()
+        .and_hms_nano_opt(23, 59, 59, 999_999_999)
+        .unwrap()
+}
+
+pub fn start_of_next_day(value: NaiveDateTime) -> Option<NaiveDateTime> {
+    start_of_day(value).checked_add_signed(Duration::days(1))
+}
+
+pub fn start_of_month(value: NaiveDateTime) -> NaiveDateTime {
+    start_of_day(value.with_day(1).unwrap())
+}
+
+pub fn add_business_days(value: NaiveDateTime, days: i64) -> NaiveDateTime {
+    let step = if days < 0 { -1 } else { 1 };
+    let mut value = value;
+    let mut remaining = days.abs();
+
+    while remaining > 0 {
+        value += Duration::days(step);
+
+        if !matches!(value.weekday(), Weekday::Sat | Weekday::Sun) {
+            remaining -= 1;
+        }
+    }
+
+    value
+}
+
+pub fn humanize(value: NaiveDateTime) -> String {
+    humanize_between(value, now())
+}
+
+pub fn humanize_between(value: NaiveDateTime, reference: NaiveDateTime) -> String {
+    let seconds = (reference - value).num_seconds();
+    let future = seconds < 0;
+    let seconds = seconds.abs();
+
+    let (amount, unit) = match seconds {
+        0..=9 => return "just now".to_string(),
+        10..=59 => (seconds, "second"),
+        60..=3_599 => (seconds / 60, "minute"),
+        3_600..=86_399 => (seconds / 3_600, "hour"),
+        86_400..=604_799 => (seconds / 86_400, "day"),
+        _ => (seconds / 604_800, "week"),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+
+    if future {
+        format!("in {amount} {unit}{plural}")
+    } else {
+        format!("{amount} {unit}{plural} ago")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -45,4 +125,108 @@ mod test {
         assert_eq!(now, from_unix);
         assert_eq!(from_str, from_unix);
     }
+
+    #[cfg(feature = "db")]
+    #[test]
+    fn db() {
+        use chrono::{FixedOffset, Local};
+
+        let now = now();
+
+        assert_eq!(from_db(to_db(now)), Some(now));
+        assert_eq!(
+            from_db(Value::BigInt(Some(now.timestamp_millis()))).map(format),
+            Some(format(now))
+        );
+        assert_eq!(from_db(Value::ChronoDateTime(None)), None);
+
+        let offset = FixedOffset::east_opt(7 * 3600).unwrap();
+        let utc = now.and_utc();
+
+        assert_eq!(
+            from_db(Value::ChronoDateTimeWithTimeZone(Some(Box::new(
+                utc.with_timezone(&offset)
+            )))),
+            Some(now)
+        );
+        assert_eq!(
+            from_db(Value::ChronoDateTimeLocal(Some(Box::new(
+                utc.with_timezone(&Local)
+            )))),
+            Some(now)
+        );
+        assert_eq!(
+            from_db(Value::ChronoDateTimeUtc(Some(Box::new(utc)))),
+            Some(now)
+        );
+        assert_eq!(
+            from_db(Value::String(Some(Box::new("now".to_string())))),
+            None
+        );
+    }
+
+    #[test]
+    fn truncation() {
+        let value = from_str("2024-02-15 13:45:10", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(format(start_of_day(value)), "2024-02-15 00:00:00");
+        assert_eq!(
+            end_of_day(value).to_string(),
+            "2024-02-15 23:59:59.999999999"
+        );
+        assert_eq!(
+            start_of_next_day(value).map(format).unwrap(),
+            "2024-02-16 00:00:00"
+        );
+
+        let last = from_str("2024-02-29 23:59:59.999999999", "%Y-%m-%d %H:%M:%S%.f").unwrap();
+
+        assert_eq!(end_of_day(last), last);
+        assert!(last < start_of_next_day(last).unwrap());
+        assert_eq!(
+            start_of_next_day(last).map(format).unwrap(),
+            "2024-03-01 00:00:00"
+        );
+        assert_eq!(
+            end_of_day(NaiveDateTime::MAX).date(),
+            NaiveDateTime::MAX.date()
+        );
+        assert_eq!(start_of_next_day(NaiveDateTime::MAX), None);
+        assert_eq!(format(start_of_month(value)), "2024-02-01 00:00:00");
+    }
+
+    #[test]
+    fn business_days() {
+        // 2024-02-16 is a Friday
+        let friday = from_str("2024-02-16 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(format(add_business_days(friday, 0)), "2024-02-16 09:00:00");
+        assert_eq!(format(add_business_days(friday, 1)), "2024-02-19 09:00:00");
+        assert_eq!(format(add_business_days(friday, 6)), "2024-02-26 09:00:00");
+        assert_eq!(format(add_business_days(friday, -5)), "2024-02-09 09:00:00");
+
+        let saturday = from_str("2024-02-17 09:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            format(add_business_days(saturday, 1)),
+            "2024-02-19 09:00:00"
+        );
+    }
+
+    #[test]
+    fn humanized() {
+        let reference = now();
+        let ago = |seconds| humanize_between(reference - Duration::seconds(seconds), reference);
+
+        assert_eq!(ago(3), "just now");
+        assert_eq!(ago(45), "45 seconds ago");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(3 * 60), "3 minutes ago");
+        assert_eq!(ago(2 * 3_600), "2 hours ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(3 * 604_800), "3 weeks ago");
+        assert_eq!(ago(-5 * 60), "in 5 minutes");
+        assert_eq!(ago(-86_400), "in 1 day");
+        assert_eq!(humanize(reference - Duration::hours(1)), "1 hour ago");
+    }
 }
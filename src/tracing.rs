@@ -1,12 +1,45 @@
 pub use tracing::{debug, error, info, trace, warn};
 
+use std::env;
+
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
 
 pub fn init() {
+    init_with_level("trace");
+}
+
+pub fn init_with_level<L: AsRef<str>>(level: L) {
     dotenvy::dotenv().ok();
 
     tracing_subscriber::registry()
+        .with(filter(level))
         .with(tracing_subscriber::fmt::layer().with_thread_ids(true))
         .init();
 }
+
+pub fn filter<L: AsRef<str>>(level: L) -> EnvFilter {
+    filter_from(env::var(EnvFilter::DEFAULT_ENV).ok(), level)
+}
+
+fn filter_from<D: AsRef<str>, L: AsRef<str>>(directives: Option<D>, level: L) -> EnvFilter {
+    directives
+        .and_then(|directives| EnvFilter::try_new(directives.as_ref()).ok())
+        .unwrap_or_else(|| EnvFilter::new(level.as_ref()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn precedence() {
+        assert_eq!(filter_from(None::<&str>, "info").to_string(), "info");
+        assert_eq!(
+            filter_from(Some("warn,lighter_common=debug"), "info").to_string(),
+            "lighter_common=debug,warn"
+        );
+        assert_eq!(filter_from(Some("=["), "info").to_string(), "info");
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "derive")]
+
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Clone, Deserialize, Serialize, ToSchema, CursorPaginationRequest, CursorPaginationResponse,
+)]
+pub struct User {
+    id: u64,
+    name: String,
+}
+
+fn users() -> Vec<User> {
+    vec![
+        User {
+            id: 1,
+            name: "John".to_string(),
+        },
+        User {
+            id: 2,
+            name: "Jane".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn next_cursor() {
+    let response = UserCursorPaginationResponse::new(users(), 2, |user| user.id).unwrap();
+    let cursor = response.next_cursor.clone().unwrap();
+    let query = format!("after={cursor}&limit=2");
+    let request = QueryParam::<UserCursorPaginationRequest>::from_query(&query).unwrap();
+
+    assert_eq!(request.after::<u64>(), Ok(Some(2)));
+    assert_eq!(request.limit(), 2);
+}
+
+#[test]
+fn last_page() {
+    let response = UserCursorPaginationResponse::new(users(), 10, |user| user.id).unwrap();
+
+    assert!(response.next_cursor.is_none());
+    assert_eq!(
+        serde_json::to_value(&response).unwrap()["nextCursor"],
+        serde_json::Value::Null
+    );
+}
+
+#[test]
+fn defaults() {
+    let request = QueryParam::<UserCursorPaginationRequest>::from_query("limit=5000").unwrap();
+
+    assert_eq!(request.after::<u64>(), Ok(None));
+    assert_eq!(request.limit(), 1000);
+}
+
+#[test]
+fn tampered() {
+    let request = QueryParam::<UserCursorPaginationRequest>::from_query("after=0OIl").unwrap();
+    let error = request.after::<u64>().unwrap_err();
+
+    assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+    assert_eq!(error.message(), Some("Invalid cursor"));
+
+    let cursor = lighter_common::base58::to_string("not json");
+    let request =
+        QueryParam::<UserCursorPaginationRequest>::from_query(&format!("after={cursor}")).unwrap();
+
+    assert!(request.after::<u64>().is_err());
+}
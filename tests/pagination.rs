@@ -0,0 +1,448 @@
+#![cfg(feature = "derive")]
+
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Clone, Deserialize, Serialize, ToSchema, PaginationRequest, PaginationResponse)]
+pub struct User {
+    id: u64,
+    #[order]
+    name: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, ToSchema, PaginationRequest)]
+#[pagination(max_limit = 200, default_limit = 25, default_page = 2)]
+pub struct Post {
+    id: u64,
+    #[order]
+    #[search]
+    title: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, ToSchema, PaginationRequest)]
+pub struct Member {
+    id: u64,
+    #[order(rename = "name")]
+    full_name: String,
+    #[order(default)]
+    joined_at: u64,
+}
+
+#[derive(Clone, Deserialize, Serialize, ToSchema, PaginationRequest)]
+#[pagination(timestamp_field = "inserted_at", default_sort = "asc")]
+pub struct Comment {
+    id: u64,
+    #[order]
+    body: String,
+    #[order]
+    inserted_at: u64,
+}
+
+#[test]
+fn limit() {
+    let request = QueryParam::<UserPaginationRequest>::from_query("").unwrap();
+
+    assert_eq!(request.page(), 1);
+    assert_eq!(request.limit(), 10);
+
+    let request = QueryParam::<UserPaginationRequest>::from_query("limit=5000").unwrap();
+
+    assert_eq!(request.limit(), 1000);
+}
+
+#[test]
+fn configured_limit() {
+    let request = QueryParam::<PostPaginationRequest>::from_query("").unwrap();
+
+    assert_eq!(request.page(), 2);
+    assert_eq!(request.limit(), 25);
+    assert_eq!(request.offset(), 25);
+
+    let request = QueryParam::<PostPaginationRequest>::from_query("limit=500").unwrap();
+
+    assert_eq!(request.limit(), 200);
+}
+
+#[test]
+fn rename() {
+    let request = QueryParam::<MemberPaginationRequest>::from_query("order=name").unwrap();
+
+    assert!(matches!(request.order(), MemberPaginationOrder::Name));
+    assert!(QueryParam::<MemberPaginationRequest>::from_query("order=fullName").is_err());
+}
+
+#[test]
+fn default_order() {
+    let request = QueryParam::<MemberPaginationRequest>::from_query("").unwrap();
+
+    assert!(matches!(request.order(), MemberPaginationOrder::JoinedAt));
+
+    let request = QueryParam::<UserPaginationRequest>::from_query("").unwrap();
+
+    assert!(matches!(request.order(), UserPaginationOrder::CreatedAt));
+}
+
+#[test]
+fn column() {
+    assert_eq!(UserPaginationOrder::CreatedAt.column(), "created_at");
+    assert_eq!(UserPaginationOrder::Name.column(), "name");
+    assert_eq!(MemberPaginationOrder::Name.column(), "full_name");
+    assert_eq!(MemberPaginationOrder::JoinedAt.column(), "joined_at");
+}
+
+#[test]
+fn boundaries() {
+    let first = UserPaginationResponse::new(vec![], 25, 1, 3);
+
+    assert!(first.has_next);
+    assert!(!first.has_prev);
+
+    let middle = UserPaginationResponse::new(vec![], 25, 2, 3);
+
+    assert!(middle.has_next);
+    assert!(middle.has_prev);
+
+    let last = UserPaginationResponse::new(vec![], 25, 3, 3);
+    let json = serde_json::to_value(&last).unwrap();
+
+    assert_eq!(json["hasNext"], false);
+    assert_eq!(json["hasPrev"], true);
+}
+
+#[cfg(feature = "sqlite")]
+mod post {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "posts")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub title: String,
+        pub created_at: i64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[cfg(feature = "sqlite")]
+async fn posts() -> DatabaseConnection {
+    use sea_orm::ConnectionTrait;
+
+    let db = database::memory().await.unwrap();
+
+    db.execute_unprepared(
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT NOT NULL, created_at INTEGER NOT NULL)",
+    )
+    .await
+    .unwrap();
+    db.execute_unprepared(
+        "INSERT INTO posts VALUES (1, 'd', 1), (2, 'b', 2), (3, 'a', 3), (4, 'c', 4), (5, 'e', 5), (6, 'Rust 100%', 6)",
+    )
+    .await
+    .unwrap();
+
+    db
+}
+
+#[cfg(feature = "sqlite")]
+#[actix_web::test]
+async fn apply() {
+    use sea_orm::EntityTrait;
+
+    let db = posts().await;
+    let request =
+        QueryParam::<PostPaginationRequest>::from_query("page=2&limit=2&order=title&sort=asc")
+            .unwrap();
+    let posts = request
+        .apply(post::Entity::find(), |order| match order {
+            PostPaginationOrder::CreatedAt => post::Column::CreatedAt,
+            PostPaginationOrder::Title => post::Column::Title,
+        })
+        .all(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        posts
+            .iter()
+            .map(|post| post.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b", "c"]
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Deserialize, Serialize, ToSchema, PaginationResponse)]
+pub struct Title {
+    title: String,
+}
+
+#[cfg(feature = "sqlite")]
+impl From<post::Model> for Title {
+    fn from(post: post::Model) -> Self {
+        Self { title: post.title }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+#[actix_web::test]
+async fn from_paginator() {
+    use sea_orm::{EntityTrait, PaginatorTrait, QueryOrder};
+
+    let db = posts().await;
+    let paginator = post::Entity::find()
+        .order_by_asc(post::Column::Id)
+        .paginate(&db, 4);
+    let response = TitlePaginationResponse::from_paginator(paginator, 2)
+        .await
+        .unwrap();
+
+    assert_eq!(response.total, 6);
+    assert_eq!(response.page, 2);
+    assert_eq!(response.pages, 2);
+    assert!(!response.has_next);
+    assert!(response.has_prev);
+    assert_eq!(
+        response
+            .data
+            .iter()
+            .map(|title| title.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["e", "Rust 100%"]
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[actix_web::test]
+async fn search() {
+    use sea_orm::{EntityTrait, QueryFilter};
+
+    let db = posts().await;
+    let request = QueryParam::<PostPaginationRequest>::from_query("search=RUST").unwrap();
+    let condition = request.search_condition::<post::Entity>().unwrap();
+    let posts = post::Entity::find()
+        .filter(condition)
+        .all(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].title, "Rust 100%");
+
+    let request = QueryParam::<PostPaginationRequest>::from_query("search=0%25").unwrap();
+    let condition = request.search_condition::<post::Entity>().unwrap();
+    let posts = post::Entity::find()
+        .filter(condition)
+        .all(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(posts.len(), 1);
+
+    let request = QueryParam::<PostPaginationRequest>::from_query("search=%25").unwrap();
+    let condition = request.search_condition::<post::Entity>().unwrap();
+    let posts = post::Entity::find()
+        .filter(condition)
+        .all(&db)
+        .await
+        .unwrap();
+
+    assert_eq!(posts.len(), 1);
+
+    let request = QueryParam::<UserPaginationRequest>::from_query("search=john").unwrap();
+
+    assert!(request.search_condition::<post::Entity>().is_none());
+}
+
+#[test]
+fn sorts() {
+    let request =
+        QueryParam::<MemberPaginationRequest>::from_query("sorts=name:asc,createdAt:desc,joinedAt")
+            .unwrap();
+    let sorts = request.sorts();
+
+    assert_eq!(sorts.len(), 3);
+    assert!(matches!(
+        sorts[0],
+        (MemberPaginationOrder::Name, Order::Asc)
+    ));
+    assert!(matches!(
+        sorts[1],
+        (MemberPaginationOrder::CreatedAt, Order::Desc)
+    ));
+    assert!(matches!(
+        sorts[2],
+        (MemberPaginationOrder::JoinedAt, Order::Desc)
+    ));
+    assert_eq!(
+        serde_json::to_value(&*request).unwrap()["sorts"],
+        "name:asc,createdAt:desc,joinedAt:desc"
+    );
+}
+
+#[test]
+fn single_sort() {
+    let request = QueryParam::<MemberPaginationRequest>::from_query("order=name&sort=asc").unwrap();
+    let sorts = request.sorts();
+
+    assert_eq!(sorts.len(), 1);
+    assert!(matches!(
+        sorts[0],
+        (MemberPaginationOrder::Name, Order::Asc)
+    ));
+}
+
+#[test]
+fn invalid_sorts() {
+    assert!(QueryParam::<MemberPaginationRequest>::from_query("sorts=name:up").is_err());
+    assert!(QueryParam::<MemberPaginationRequest>::from_query("sorts=fullName:asc").is_err());
+}
+
+#[test]
+fn case_insensitive() {
+    for query in [
+        "order=createdAt&sort=asc",
+        "order=createdat&sort=ASC",
+        "order=CreatedAt&sort=Asc",
+        "order=CREATEDAT&sort=aSc",
+    ] {
+        let request = QueryParam::<UserPaginationRequest>::from_query(query).unwrap();
+
+        assert!(matches!(request.order(), UserPaginationOrder::CreatedAt));
+        assert!(matches!(request.sort(), Order::Asc));
+    }
+
+    let request = QueryParam::<MemberPaginationRequest>::from_query("sorts=NAME:DESC").unwrap();
+
+    assert!(matches!(
+        request.sorts()[0],
+        (MemberPaginationOrder::Name, Order::Desc)
+    ));
+    assert_eq!(
+        serde_json::to_value(UserPaginationOrder::CreatedAt).unwrap(),
+        "createdAt"
+    );
+}
+
+#[test]
+fn params() {
+    let params = PostPaginationRequest::into_params(|| None)
+        .into_iter()
+        .map(|param| serde_json::to_value(param).unwrap())
+        .collect::<Vec<_>>();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|param| param["name"] == name)
+            .cloned()
+            .unwrap()
+    };
+
+    let page = param("page");
+
+    assert_eq!(page["required"], false);
+    assert_eq!(page["schema"]["minimum"], 1.0);
+    assert_eq!(page["example"], 2);
+
+    let limit = param("limit");
+
+    assert_eq!(limit["required"], false);
+    assert_eq!(limit["schema"]["minimum"], 1.0);
+    assert_eq!(limit["schema"]["maximum"], 200.0);
+    assert_eq!(limit["example"], 25);
+
+    for name in ["search", "sort", "order", "sorts"] {
+        assert_eq!(param(name)["required"], false);
+    }
+}
+
+#[test]
+fn timestamp_field() {
+    let request = QueryParam::<CommentPaginationRequest>::from_query("").unwrap();
+
+    assert!(matches!(
+        request.order(),
+        CommentPaginationOrder::InsertedAt
+    ));
+    assert_eq!(CommentPaginationOrder::InsertedAt.column(), "inserted_at");
+    assert_eq!(CommentPaginationOrder::Body.column(), "body");
+    assert!(QueryParam::<CommentPaginationRequest>::from_query("order=insertedAt").is_ok());
+    assert!(QueryParam::<CommentPaginationRequest>::from_query("order=createdAt").is_err());
+}
+
+#[test]
+fn default() {
+    let request = UserPaginationRequest::default();
+
+    assert_eq!(request.page(), 1);
+    assert_eq!(request.limit(), 10);
+    assert!(request.search().is_none());
+    assert!(matches!(request.order(), UserPaginationOrder::CreatedAt));
+
+    let request = PostPaginationRequest::default();
+
+    assert_eq!(request.page(), 2);
+    assert_eq!(request.limit(), 25);
+
+    let request = UserPaginationRequest::new(3, 20);
+
+    assert_eq!(request.page(), 3);
+    assert_eq!(request.limit(), 20);
+    assert_eq!(request.offset(), 40);
+}
+
+#[test]
+fn validate() {
+    let request = QueryParam::<UserPaginationRequest>::from_query("page=1&limit=5").unwrap();
+
+    assert!(request.validate().is_ok());
+
+    let request = QueryParam::<UserPaginationRequest>::from_query("page=0").unwrap();
+    let validation = request.validate().unwrap_err();
+
+    assert_eq!(validation.get("page"), vec!["Page must be at least 1"]);
+    assert!(validation.get("limit").is_empty());
+    assert_eq!(request.offset(), 0);
+
+    let request = QueryParam::<UserPaginationRequest>::from_query("page=0&limit=0").unwrap();
+    let validation = request.validate().unwrap_err();
+
+    assert!(validation.has_error("page"));
+    assert!(validation.has_error("limit"));
+}
+
+#[test]
+fn offset_overflow() {
+    let request =
+        QueryParam::<UserPaginationRequest>::from_query("page=18446744073709551615&limit=10")
+            .unwrap();
+
+    assert_eq!(request.offset(), u64::MAX);
+}
+
+#[test]
+fn default_sort() {
+    let request = QueryParam::<UserPaginationRequest>::from_query("").unwrap();
+
+    assert!(matches!(request.sort(), Order::Desc));
+
+    let request = QueryParam::<CommentPaginationRequest>::from_query("").unwrap();
+
+    assert!(matches!(request.sort(), Order::Asc));
+
+    let request = QueryParam::<CommentPaginationRequest>::from_query("sort=desc").unwrap();
+
+    assert!(matches!(request.sort(), Order::Desc));
+
+    let request = QueryParam::<CommentPaginationRequest>::from_query("sorts=body").unwrap();
+
+    assert!(matches!(
+        request.sorts()[0],
+        (CommentPaginationOrder::Body, Order::Asc)
+    ));
+}